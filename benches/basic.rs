@@ -2,14 +2,16 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use rand::{rng, Rng};
+use slab::Slab;
 use slotmap::{DefaultKey, DenseSlotMap, HopSlotMap, SlotMap};
-use basico::VecPool;
+use basico::{DenseVecPool, VecPool};
 
 fn inserts(c: &mut Criterion) {
     let size = 10_000;
     let s4: SlotMap<DefaultKey, usize> = SlotMap::new();
     let s6: DenseSlotMap<DefaultKey, usize> = DenseSlotMap::new();
     let pool: VecPool<usize> = VecPool::new();
+    let dense: DenseVecPool<usize> = DenseVecPool::new();
 
     let mut g = c.benchmark_group("Inserts");
     g.bench_function("SlotMap", |b| {
@@ -46,6 +48,17 @@ fn inserts(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+    g.bench_function("DenseVecPool", |b| {
+        b.iter_batched_ref(
+            || dense.clone(),
+            |i| {
+                for a in 0..size {
+                    i.alloc_node(a);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
 }
 
 fn reinserts(c: &mut Criterion) {
@@ -57,17 +70,21 @@ fn reinserts(c: &mut Criterion) {
 
     let mut pool: VecPool<usize> = VecPool::new();
     let mut poolk = Vec::new();
+    let mut dense: DenseVecPool<usize> = DenseVecPool::new();
+    let mut densek = Vec::new();
     
 
     for a in 0..size {
         s4k.push(s4.insert(a));
         s6k.push(s6.insert(a));
-        poolk.push(pool.alloc_node(a));
+        poolk.push(pool.alloc_node(a).index());
+        densek.push(dense.alloc_node(a));
     }
     for a in 0..size {
         s4.remove(s4k[a]);
         s6.remove(s6k[a]);
         pool.free_node(poolk[a]);
+        dense.free_node(densek[a]);
     }
     let mut g = c.benchmark_group("Re-inserts");
     g.bench_function("SlotMap", |b| {
@@ -103,6 +120,17 @@ fn reinserts(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+    g.bench_function("DenseVecPool", |b| {
+        b.iter_batched_ref(
+            || dense.clone(),
+            |i| {
+                for a in 0..size {
+                    i.alloc_node(a);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
 }
 
 fn remove(c: &mut Criterion) {
@@ -113,12 +141,19 @@ fn remove(c: &mut Criterion) {
     let mut s6k = Vec::new();
     
     let mut pool: VecPool<usize> = VecPool::new();
+    // índices 'crus' (sem validar geração) e handles (com geração)
     let mut poolk = Vec::new();
+    let mut poolh = Vec::new();
+    let mut dense: DenseVecPool<usize> = DenseVecPool::new();
+    let mut densek = Vec::new();
     
     for a in 0..size {
         s4k.push(s4.insert(a));
         s6k.push(s6.insert(a));
-        poolk.push(pool.alloc_node(a));
+        let handle = pool.alloc_node(a);
+        poolk.push(handle.index());
+        poolh.push(handle);
+        densek.push(dense.alloc_node(a));
     }
 
     let mut g = c.benchmark_group("Remove");
@@ -156,6 +191,28 @@ fn remove(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+    g.bench_function("VecPool (geração)", |b| {
+        b.iter_batched_ref(
+            || pool.clone(),
+            |i| {
+                for a in 0..size {
+                    i.free_node(poolh[a]);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    g.bench_function("DenseVecPool", |b| {
+        b.iter_batched_ref(
+            || dense.clone(),
+            |i| {
+                for a in 0..size {
+                    i.free_node(densek[a]);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
 }
 
 fn get(c: &mut Criterion) {
@@ -169,11 +226,17 @@ fn get(c: &mut Criterion) {
     
     let mut pool: VecPool<usize> = VecPool::new();
     let mut poolk = Vec::new();
+    let mut poolh = Vec::new();
+    let mut dense: DenseVecPool<usize> = DenseVecPool::new();
+    let mut densek = Vec::new();
 
     for a in 0..size {
         s4k.push(s4.insert(a));
         s6k.push(s6.insert(a));
-        poolk.push(pool.alloc_node(a));
+        let handle = pool.alloc_node(a);
+        poolk.push(handle.index());
+        poolh.push(handle);
+        densek.push(dense.alloc_node(a));
     }
 
     let mut g = c.benchmark_group("Get");
@@ -210,17 +273,33 @@ fn get(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+    g.bench_function("VecPool (geração)", |b| {
+        b.iter_batched_ref(
+            || pool.clone(),
+            |i| {
+                for _ in 0..size {
+                    black_box(i.get_node(poolh[rng.random_range(0..size)]));
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    g.bench_function("DenseVecPool", |b| {
+        b.iter_batched_ref(
+            || dense.clone(),
+            |i| {
+                for _ in 0..size {
+                    black_box(i.get_node(densek[rng.random_range(0..size)]));
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
 }
-/*
+
 fn iter(c: &mut Criterion) {
     let size = 10_000;
-    let mut rng = thread_rng();
-    let mut s1: BvMap<usize, usize> = BvMap::new();
-    let mut s1k = Vec::new();
-    let mut s2: Stash<usize, usize> = Stash::new();
-    let mut s2k = Vec::new();
-    let mut s3: UniqueStash<usize> = UniqueStash::new();
-    let mut s3k = Vec::new();
+    let mut rng = rng();
     let mut s4: SlotMap<DefaultKey, usize> = SlotMap::new();
     let mut s4k = Vec::new();
     let mut s5: HopSlotMap<DefaultKey, usize> = HopSlotMap::new();
@@ -229,66 +308,22 @@ fn iter(c: &mut Criterion) {
     let mut s6k = Vec::new();
     let mut s7: Slab<usize> = Slab::new();
     let mut s7k = Vec::new();
-    let mut s9: ExternStableVec<usize> = ExternStableVec::new();
-    let mut s9k = Vec::new();
-    let mut s10: InlineStableVec<usize> = InlineStableVec::new();
-    let mut s10k = Vec::new();
-    let mut s11: IdVec<usize> = IdVec::new();
-    let mut s11k = Vec::new();
-    let mut s12: CompactMap<usize> = CompactMap::new();
-    let mut s12k = Vec::new();
-    let mut s14: Arena<usize> = Arena::new();
-    let mut s14k = Vec::new();
+
+    let mut pool: VecPool<usize> = VecPool::new();
+    let mut poolk = Vec::new();
+    let mut dense: DenseVecPool<usize> = DenseVecPool::new();
+    let mut densek = Vec::new();
 
     for a in 0..size {
-        s1k.push(s1.insert(a));
-        s2k.push(s2.put(a));
-        s3k.push(s3.put(a));
         s4k.push(s4.insert(a));
         s5k.push(s5.insert(a));
         s6k.push(s6.insert(a));
         s7k.push(s7.insert(a));
-        s9k.push(s9.push(a));
-        s10k.push(s10.push(a));
-        s11k.push(s11.insert(a));
-        s12k.push(s12.insert(a));
-        s14k.push(s14.insert(a));
+        poolk.push(pool.alloc_node(a));
+        densek.push(dense.alloc_node(a));
     }
 
     let mut g = c.benchmark_group("Iterate");
-    g.bench_function("BvMap", |b| {
-        b.iter_batched_ref(
-            || s1.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("Stash", |b| {
-        b.iter_batched_ref(
-            || s2.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("UniqueStash", |b| {
-        b.iter_batched_ref(
-            || s3.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
     g.bench_function("SlotMap", |b| {
         b.iter_batched_ref(
             || s4.clone(),
@@ -333,49 +368,9 @@ fn iter(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
-    g.bench_function("BeachMap", |b| {
-        b.iter_batched(
-            || {
-                let mut s8: BeachMap<usize, usize> = BeachMap::new();
-                let mut s8k = Vec::new();
-                for a in 0..size {
-                    s8k.push(s8.insert(a));
-                }
-                s8
-            },
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        );
-    });
-    g.bench_function("ExternStableVec", |b| {
-        b.iter_batched_ref(
-            || s9.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("InlineStableVec", |b| {
-        b.iter_batched_ref(
-            || s10.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("IdVec", |b| {
+    g.bench_function("VecPool", |b| {
         b.iter_batched_ref(
-            || s11.clone(),
+            || pool.clone(),
             |i| {
                 for a in i.iter() {
                     black_box(a);
@@ -384,38 +379,9 @@ fn iter(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
-    g.bench_function("CompactMap", |b| {
+    g.bench_function("DenseVecPool", |b| {
         b.iter_batched_ref(
-            || s12.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("Froggy", |b| {
-        b.iter_batched(
-            || {
-                let mut s13: Storage<usize> = Storage::new();
-                let mut s13k = Vec::new();
-                for a in 0..size {
-                    s13k.push(s13.create(a));
-                }
-                (s13, s13k)
-            },
-            |(i, _k)| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        );
-    });
-    g.bench_function("GenerationalArena", |b| {
-        b.iter_batched_ref(
-            || s14.clone(),
+            || dense.clone(),
             |i| {
                 for a in i.iter() {
                     black_box(a);
@@ -427,13 +393,7 @@ fn iter(c: &mut Criterion) {
     g.finish();
 
     for subset in ((size / 2)..size).rev() {
-        let k = rng.gen_range(0, subset);
-        s1.remove(s1k[k]);
-        s1k.swap_remove(k);
-        s2.take(s2k[k]);
-        s2k.swap_remove(k);
-        s3.take(s3k[k]);
-        s3k.swap_remove(k);
+        let k = rng.random_range(0..subset);
         s4.remove(s4k[k]);
         s4k.swap_remove(k);
         s5.remove(s5k[k]);
@@ -442,52 +402,13 @@ fn iter(c: &mut Criterion) {
         s6k.swap_remove(k);
         s7.remove(s7k[k]);
         s7k.swap_remove(k);
-        s9.remove(s9k[k]);
-        s9k.swap_remove(k);
-        s10.remove(s10k[k]);
-        s10k.swap_remove(k);
-        s11.remove(s11k[k]);
-        s11k.swap_remove(k);
-        s12.remove(s12k[k]);
-        s12k.swap_remove(k);
-        s14.remove(s14k[k]);
-        s14k.swap_remove(k);
+        pool.free_node(poolk[k]);
+        poolk.swap_remove(k);
+        dense.free_node(densek[k]);
+        densek.swap_remove(k);
     }
 
     let mut g = c.benchmark_group("Iterate half-full");
-    g.bench_function("BvMap", |b| {
-        b.iter_batched_ref(
-            || s1.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("Stash", |b| {
-        b.iter_batched_ref(
-            || s2.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("UniqueStash", |b| {
-        b.iter_batched_ref(
-            || s3.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
     g.bench_function("SlotMap", |b| {
         b.iter_batched_ref(
             || s4.clone(),
@@ -532,65 +453,9 @@ fn iter(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
-    g.bench_function("BeachMap", |b| {
-        b.iter_batched(
-            || {
-                let mut s8: BeachMap<usize, usize> = BeachMap::new();
-                let mut s8k = Vec::new();
-                for a in 0..size {
-                    s8k.push(s8.insert(a));
-                }
-                for subset in ((size / 2)..size).rev() {
-                    let k = rng.gen_range(0, subset);
-                    s8.remove(s8k[k]);
-                    s8k.swap_remove(k);
-                }
-                s8
-            },
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        );
-    });
-    g.bench_function("ExternStableVec", |b| {
-        b.iter_batched_ref(
-            || s9.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("InlineStableVec", |b| {
-        b.iter_batched_ref(
-            || s10.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("IdVec", |b| {
-        b.iter_batched_ref(
-            || s11.clone(),
-            |i| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("CompactMap", |b| {
+    g.bench_function("VecPool", |b| {
         b.iter_batched_ref(
-            || s12.clone(),
+            || pool.clone(),
             |i| {
                 for a in i.iter() {
                     black_box(a);
@@ -599,32 +464,9 @@ fn iter(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
-    g.bench_function("Froggy", |b| {
-        b.iter_batched(
-            || {
-                let mut s13: Storage<usize> = Storage::new();
-                let mut s13k = Vec::new();
-                for a in 0..size {
-                    s13k.push(s13.create(a));
-                }
-                for subset in ((size / 2)..size).rev() {
-                    let k = rng.gen_range(0, subset);
-                    s13k.swap_remove(k);
-                }
-                s13.sync_pending();
-                (s13, s13k)
-            },
-            |(i, _k)| {
-                for a in i.iter() {
-                    black_box(a);
-                }
-            },
-            BatchSize::SmallInput,
-        );
-    });
-    g.bench_function("GenerationalArena", |b| {
+    g.bench_function("DenseVecPool", |b| {
         b.iter_batched_ref(
-            || s14.clone(),
+            || dense.clone(),
             |i| {
                 for a in i.iter() {
                     black_box(a);
@@ -633,7 +475,7 @@ fn iter(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
-}*/
+}
 
-criterion_group!(benches, inserts, reinserts, remove, get);//, iter);
+criterion_group!(benches, inserts, reinserts, remove, get, iter);
 criterion_main!(benches);
\ No newline at end of file
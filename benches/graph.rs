@@ -0,0 +1,161 @@
+use std::{alloc::System, collections::HashSet};
+
+use basico::{GraphIter, GraphIterState, GraphSearch, ParentLink, Stack, Vec2D, VisitedSet};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use stats_alloc::{StatsAlloc, INSTRUMENTED_SYSTEM};
+
+type Pos = (i32, i32);
+
+/**
+ * Labirinto perfeito (só um caminho entre duas posições), igual ao generate_maze do dungeon
+ * true: chão, false: parede
+ */
+fn maze(size: usize) -> Vec2D<bool> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut tiles = Vec2D::new(size, size, false);
+    let (mw, mh) = (size as i32 / 2, size as i32 / 2);
+
+    let mut iter = GraphSearch::<Pos>::depth_first((0, 0));
+    while let Some(path) = iter.next() {
+        let mut path = path.clone();
+        let (mx, my) = path.pop().unwrap();
+        let (m1x, m1y) = path.pop().unwrap_or((mx, my));
+
+        tiles[((mx * 2) as usize, (my * 2) as usize)] = true;
+        tiles[((m1x + mx) as usize, (m1y + my) as usize)] = true;
+
+        let mut neighs = [(mx+1, my), (mx, my-1), (mx-1, my), (mx, my+1)];
+        neighs.shuffle(&mut rng);
+        for neigh in neighs {
+            if neigh.0 >= 0 && neigh.0 < mw && neigh.1 >= 0 && neigh.1 < mh {
+                iter.push_neighbor(neigh);
+            }
+        }
+    }
+    return tiles;
+}
+
+fn visit<S: GraphIterState<Pos>>(tiles: &Vec2D<bool>, (x, y): Pos, iter_state: &mut S) {
+    for neigh in [(x+1, y), (x, y-1), (x-1, y), (x, y+1)] {
+        if tiles.get(neigh) == Some(&true) {
+            iter_state.push_neighbor(neigh);
+        }
+    }
+}
+
+/**
+ * on_step é chamado a cada vértice explorado (usado para medir a memória durante a busca)
+ */
+fn solve_stack<F: FnMut()>(tiles: &Vec2D<bool>, goal: Pos, mut search: GraphSearch<Pos>, mut on_step: F) -> usize {
+    while let Some(path) = search.next() {
+        on_step();
+        let pos = *path.peek().unwrap();
+        if pos == goal { break; }
+        visit(tiles, pos, &mut search);
+    }
+    return search.get_path().len() as usize;
+}
+
+fn solve_parents<F: FnMut()>(tiles: &Vec2D<bool>, goal: Pos, mut search: GraphSearch<Pos, usize, ParentLink<Pos>>, mut on_step: F) -> usize {
+    while let Some(link) = search.next() {
+        on_step();
+        let pos = *link.peek().unwrap();
+        if pos == goal { break; }
+        visit(tiles, pos, &mut search);
+    }
+    return search.get_path().len() as usize;
+}
+
+/**
+ * Visita o labirinto inteiro, retorna quantas posições foram visitadas
+ */
+fn flood<V: VisitedSet<Pos>>(tiles: &Vec2D<bool>, iter: &mut GraphIter<Pos, usize, V>) -> usize {
+    let mut count = 0;
+    while let Some(pos) = iter.next() {
+        count += 1;
+        visit(tiles, pos, iter);
+    }
+    return count;
+}
+
+// Conta os bytes pedidos ao alocador, o RSS do processo não serve pois o malloc não devolve as páginas liberadas
+#[global_allocator]
+static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
+
+/**
+ * Bytes alocados no momento (o stats_alloc só tem os totais, não o pico)
+ */
+fn allocated_bytes() -> isize {
+    let stats = GLOBAL.stats();
+    return stats.bytes_allocated as isize - stats.bytes_deallocated as isize + stats.bytes_reallocated;
+}
+
+/**
+ * Pico de memória (KB) alocada durante a busca, além do que já estava alocado antes
+ * f recebe o on_step da busca, o pico é o maior valor visto a cada vértice explorado
+ */
+fn peak_memory_kb<F: FnOnce(&mut dyn FnMut()) -> usize>(f: F) -> (isize, usize) {
+    let before = allocated_bytes();
+    let mut peak = before;
+    let result = f(&mut || peak = peak.max(allocated_bytes()));
+    return ((peak - before) / 1024, result);
+}
+
+fn graph_search(c: &mut Criterion) {
+    let size = 1000;
+    let tiles = maze(size);
+    // Canto oposto, as posições do labirinto são as pares
+    let (start, goal) = ((0, 0), (size as i32 - 2, size as i32 - 2));
+
+    // Memória não é medida pelo criterion, então só mostra uma vez, em uma linha
+    let (stack_bfs, len) = peak_memory_kb(|step| solve_stack(&tiles, goal, GraphSearch::breadth_first(start), step));
+    let (parents_bfs, _) = peak_memory_kb(|step| solve_parents(&tiles, goal, GraphSearch::breadth_first(start).with_parent_map(), step));
+    let (stack_dfs, _) = peak_memory_kb(|step| solve_stack(&tiles, goal, GraphSearch::depth_first(start), step));
+    let (parents_dfs, _) = peak_memory_kb(|step| solve_parents(&tiles, goal, GraphSearch::depth_first(start).with_parent_map(), step));
+    println!("Pico de memória (caminho com {} posições): LinkedStack BFS {} KB, ParentMap BFS {} KB, LinkedStack DFS {} KB, ParentMap DFS {} KB",
+        len, stack_bfs, parents_bfs, stack_dfs, parents_dfs);
+
+    let mut g = c.benchmark_group("GraphSearch Maze");
+    g.sample_size(10);
+    g.bench_function("LinkedStack BFS", |b| {
+        b.iter(|| black_box(solve_stack(&tiles, goal, GraphSearch::breadth_first(start), || {})))
+    });
+    g.bench_function("ParentMap BFS", |b| {
+        b.iter(|| black_box(solve_parents(&tiles, goal, GraphSearch::breadth_first(start).with_parent_map(), || {})))
+    });
+    g.bench_function("LinkedStack DFS", |b| {
+        b.iter(|| black_box(solve_stack(&tiles, goal, GraphSearch::depth_first(start), || {})))
+    });
+    g.bench_function("ParentMap DFS", |b| {
+        b.iter(|| black_box(solve_parents(&tiles, goal, GraphSearch::depth_first(start).with_parent_map(), || {})))
+    });
+    g.finish();
+
+    let mut g = c.benchmark_group("GraphIter Visited");
+    g.sample_size(10);
+    g.bench_function("HashSet", |b| {
+        b.iter(|| black_box(flood(&tiles, &mut GraphIter::breadth_first(start))))
+    });
+    g.bench_function("Vec2D<bool>", |b| {
+        b.iter(|| black_box(flood(&tiles, &mut GraphIter::breadth_first(start).with_visited(Vec2D::new(size, size, false)))))
+    });
+    // Reaproveitando o mesmo GraphIter, sem alocar de novo
+    let mut iter: GraphIter<Pos, usize, HashSet<Pos>> = GraphIter::breadth_first(start);
+    g.bench_function("HashSet reset", |b| {
+        b.iter(|| {
+            iter.reset(start);
+            black_box(flood(&tiles, &mut iter))
+        })
+    });
+    let mut iter = GraphIter::breadth_first(start).with_visited(Vec2D::new(size, size, false));
+    g.bench_function("Vec2D<bool> reset", |b| {
+        b.iter(|| {
+            iter.reset(start);
+            black_box(flood(&tiles, &mut iter))
+        })
+    });
+}
+
+criterion_group!(benches, graph_search);
+criterion_main!(benches);
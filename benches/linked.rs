@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use basico::{Queue, Stack};
+use basico::{Deque, Queue, RingDeque, Stack};
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use rand::{rng, Rng};
 
@@ -92,6 +92,24 @@ fn linked_list(c: &mut Criterion) {
         );
     });
 
+    g.bench_function("Ring Deque", |b| {
+        b.iter_batched_ref(
+            || RingDeque::new(),
+            |i| {
+                for a in 0..size {
+                    i.push_back(a);
+                    i.push_front(a);
+                    i.push_back(a);
+                    i.push_front(a);
+
+                    i.pop_front();
+                    i.pop_back();
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
     g.bench_function("Linked List (Rust)", |b| {
         b.iter_batched_ref(
             ||  std::collections::LinkedList::new(),
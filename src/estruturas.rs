@@ -7,14 +7,47 @@ pub use dir::*;
 mod stack;
 pub use stack::*;
 
+mod sync_stack;
+pub use sync_stack::*;
+
 mod queue;
 pub use queue::*;
 
+mod deque;
+pub use deque::*;
+
+mod priority_queue;
+pub use priority_queue::*;
+
+mod blocking_queue;
+pub use blocking_queue::*;
+
+mod persistent_queue;
+pub use persistent_queue::*;
+
 mod linked_list;
 pub use linked_list::*;
 
 mod vecpool;
 pub use vecpool::*;
 
+mod dense_vecpool;
+pub use dense_vecpool::*;
+
+mod lru_cache;
+pub use lru_cache::*;
+
+mod union_find;
+pub use union_find::*;
+
+mod visited_set;
+pub use visited_set::*;
+
 mod graph;
-pub use graph::*;
\ No newline at end of file
+pub use graph::*;
+
+mod graph_algorithms;
+pub use graph_algorithms::*;
+
+mod graph_format;
+pub use graph_format::*;
\ No newline at end of file
@@ -0,0 +1,360 @@
+use std::{error::Error, fmt, sync::{Condvar, Mutex, MutexGuard, PoisonError}, time::{Duration, Instant}};
+
+use super::{Deque, RingDeque};
+
+/**
+ * Fila limitada para várias threads (produtor/consumidor), com Mutex + Condvar
+ *
+ * Funcionamento:
+ * - Os valores ficam em um RingDeque protegido pelo Mutex, todos os métodos recebem &self (compartilhar com Arc)
+ * - enqueue espera (dorme na Condvar not_full) enquanto a fila estiver cheia
+ * - dequeue espera (dorme na Condvar not_empty) enquanto a fila estiver vazia
+ * - Cada operação acorda uma thread esperando do outro lado
+ *
+ * close() marca a fila como fechada e acorda todo mundo que está esperando:
+ * - enqueue passa a retornar erro com o valor de volta
+ * - dequeue continua retornando o que sobrou, e depois None (fim para os consumidores)
+ *
+ * Não implementa o trait Queue, pois head()/tail() retornam referências que não podem sair do Mutex
+ */
+pub struct BlockingQueue<T> {
+    state: Mutex<BlockingState<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize
+}
+
+struct BlockingState<T> {
+    values: RingDeque<T>,
+    closed: bool
+}
+
+/**
+ * Erro ao inserir, devolve o valor
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnqueueError<T> {
+    // Fila cheia (somente try_enqueue e enqueue_timeout, enqueue espera)
+    Full(T),
+    // Fila fechada com close()
+    Closed(T)
+}
+
+impl<T> EnqueueError<T> {
+    pub fn into_value(self) -> T {
+        match self {
+            EnqueueError::Full(value) | EnqueueError::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Display for EnqueueError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EnqueueError::Full(_) => write!(f, "a fila está cheia"),
+            EnqueueError::Closed(_) => write!(f, "a fila foi fechada"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for EnqueueError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DequeueError {
+    // Fila vazia (somente try_dequeue)
+    Empty,
+    // Passou o tempo limite de dequeue_timeout sem nenhum valor
+    Timeout,
+    // Fila fechada e sem mais valores
+    Closed
+}
+
+impl fmt::Display for DequeueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DequeueError::Empty => write!(f, "a fila está vazia"),
+            DequeueError::Timeout => write!(f, "tempo esgotado esperando um valor"),
+            DequeueError::Closed => write!(f, "a fila foi fechada"),
+        }
+    }
+}
+
+impl Error for DequeueError {}
+
+impl<T> BlockingQueue<T> {
+    /**
+     * Panic se capacity for 0 (enqueue esperaria para sempre)
+     */
+    pub fn new(capacity: usize) -> BlockingQueue<T> {
+        assert!(capacity > 0, "BlockingQueue: capacidade deve ser maior que 0");
+        BlockingQueue {
+            state: Mutex::new(BlockingState { values: RingDeque::with_capacity(capacity), closed: false }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity: capacity
+        }
+    }
+
+    /**
+     * Se outra thread deu panic segurando o Mutex, continua usando o estado (nenhuma operação deixa ele pela metade)
+     */
+    fn lock(&self) -> MutexGuard<'_, BlockingState<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /**
+     * Insere no final, esperando enquanto a fila estiver cheia
+     * Retorna erro (com o valor) se a fila estiver ou for fechada enquanto espera
+     */
+    pub fn enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+        let mut state = self.lock();
+        while !state.closed && state.values.len() >= self.capacity {
+            state = self.not_full.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        return self.push_locked(state, value);
+    }
+
+    /**
+     * Insere sem esperar, erro Full se a fila estiver cheia
+     */
+    pub fn try_enqueue(&self, value: T) -> Result<(), EnqueueError<T>> {
+        let state = self.lock();
+        if !state.closed && state.values.len() >= self.capacity {
+            return Err(EnqueueError::Full(value));
+        }
+        return self.push_locked(state, value);
+    }
+
+    /**
+     * Insere esperando no máximo timeout por espaço, erro Full se o tempo acabar
+     */
+    pub fn enqueue_timeout(&self, value: T, timeout: Duration) -> Result<(), EnqueueError<T>> {
+        // Timeout grande demais para um Instant (ex: Duration::MAX) é o mesmo que esperar sem limite
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.enqueue(value);
+        };
+        let mut state = self.lock();
+        while !state.closed && state.values.len() >= self.capacity {
+            let now = Instant::now();
+            if now >= deadline { return Err(EnqueueError::Full(value)); }
+            state = self.not_full.wait_timeout(state, deadline - now).unwrap_or_else(PoisonError::into_inner).0;
+        }
+        return self.push_locked(state, value);
+    }
+
+    fn push_locked(&self, mut state: MutexGuard<'_, BlockingState<T>>, value: T) -> Result<(), EnqueueError<T>> {
+        if state.closed {
+            return Err(EnqueueError::Closed(value));
+        }
+        state.values.push_back(value);
+        drop(state);
+        self.not_empty.notify_one();
+        return Ok(());
+    }
+
+    /**
+     * Remove do início, esperando enquanto a fila estiver vazia
+     * Retorna None somente quando a fila está fechada e não sobrou nenhum valor
+     */
+    pub fn dequeue(&self) -> Option<T> {
+        let mut state = self.lock();
+        while !state.closed && state.values.is_empty() {
+            state = self.not_empty.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        return self.pop_locked(state).ok();
+    }
+
+    /**
+     * Remove sem esperar, erro Empty se não tiver nenhum valor
+     */
+    pub fn try_dequeue(&self) -> Result<T, DequeueError> {
+        let state = self.lock();
+        if !state.closed && state.values.is_empty() {
+            return Err(DequeueError::Empty);
+        }
+        return self.pop_locked(state);
+    }
+
+    /**
+     * Remove esperando no máximo timeout, erro Timeout se nenhum valor chegar a tempo
+     */
+    pub fn dequeue_timeout(&self, timeout: Duration) -> Result<T, DequeueError> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.dequeue().ok_or(DequeueError::Closed);
+        };
+        let mut state = self.lock();
+        while !state.closed && state.values.is_empty() {
+            let now = Instant::now();
+            if now >= deadline { return Err(DequeueError::Timeout); }
+            state = self.not_empty.wait_timeout(state, deadline - now).unwrap_or_else(PoisonError::into_inner).0;
+        }
+        return self.pop_locked(state);
+    }
+
+    fn pop_locked(&self, mut state: MutexGuard<'_, BlockingState<T>>) -> Result<T, DequeueError> {
+        match state.values.pop_front() {
+            Some(value) => {
+                drop(state);
+                self.not_full.notify_one();
+                return Ok(value);
+            },
+            None => { return Err(DequeueError::Closed); },
+        }
+    }
+
+    /**
+     * Fecha a fila: ninguém mais consegue inserir, e todas as threads esperando são acordadas
+     */
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().values.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /**
+     * Consome os valores com dequeue() até a fila ser fechada e esvaziada
+     * for value in queue.iter() { ... }
+     */
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(|| self.dequeue())
+    }
+}
+
+impl<T> fmt::Debug for BlockingQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.lock();
+        write!(f, "BlockingQueue [length:{}, capacity:{}, closed:{}]", state.values.len(), self.capacity, state.closed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use super::{BlockingQueue, DequeueError, EnqueueError};
+
+    #[test]
+    pub fn blocking_queue_methods() {
+        let queue = BlockingQueue::new(2);
+        assert_eq!(queue.try_dequeue(), Err(DequeueError::Empty));
+        assert_eq!(queue.enqueue('A'), Ok(()));
+        assert_eq!(queue.try_enqueue('B'), Ok(()));
+        assert_eq!(queue.try_enqueue('C'), Err(EnqueueError::Full('C')));
+        assert_eq!(queue.enqueue_timeout('C', Duration::from_millis(10)), Err(EnqueueError::Full('C')));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.dequeue(), Some('A'));
+        assert_eq!(queue.dequeue_timeout(Duration::from_millis(10)), Ok('B'));
+        assert_eq!(queue.dequeue_timeout(Duration::from_millis(10)), Err(DequeueError::Timeout));
+
+        // Duration::MAX não cabe em um Instant, espera sem limite (aqui não precisa esperar)
+        assert_eq!(queue.enqueue_timeout('X', Duration::MAX), Ok(()));
+        assert_eq!(queue.dequeue_timeout(Duration::MAX), Ok('X'));
+
+        queue.enqueue('D').unwrap();
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.enqueue('E').map_err(|e| e.into_value()), Err('E'));
+        assert_eq!(format!("{:?}", queue), "BlockingQueue [length:1, capacity:2, closed:true]");
+        // Os que sobraram ainda saem depois de fechar
+        assert_eq!(queue.iter().collect::<String>(), "D");
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.try_dequeue(), Err(DequeueError::Closed));
+        assert_eq!(queue.dequeue_timeout(Duration::from_secs(10)), Err(DequeueError::Closed));
+        assert_eq!(queue.dequeue_timeout(Duration::MAX), Err(DequeueError::Closed));
+        assert_eq!(queue.enqueue_timeout('F', Duration::MAX), Err(EnqueueError::Closed('F')));
+    }
+
+    #[test]
+    pub fn blocking_queue_close_wakes() {
+        let queue = Arc::new(BlockingQueue::<i32>::new(1));
+
+        // Consumidor esperando em uma fila vazia
+        let consumidor = {
+            let queue = queue.clone();
+            thread::spawn(move || queue.dequeue())
+        };
+        // Produtor esperando em uma fila cheia
+        queue.enqueue(1).unwrap();
+        let produtor = {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                // O consumidor pode pegar o 1 antes, então insere até ficar esperando
+                loop {
+                    if let Err(err) = queue.enqueue(2) { return err; }
+                }
+            })
+        };
+
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        assert_eq!(produtor.join().unwrap(), EnqueueError::Closed(2));
+        let recebido = consumidor.join().unwrap();
+        assert!(matches!(recebido, Some(1) | Some(2) | None));
+    }
+
+    #[test]
+    pub fn blocking_queue_mpmc() {
+        let queue = Arc::new(BlockingQueue::new(8));
+        let produtores = 4;
+        let por_produtor = 2500;
+
+        let producers: Vec<_> = (0..produtores).map(|p| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                for i in 0..por_produtor {
+                    queue.enqueue(p * por_produtor + i).unwrap();
+                }
+            })
+        }).collect();
+
+        let consumers: Vec<_> = (0..3).map(|_| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                let mut recebidos = Vec::new();
+                for value in queue.iter() {
+                    assert!(queue.len() <= queue.capacity());
+                    recebidos.push(value);
+                }
+                recebidos
+            })
+        }).collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+
+        let mut todos: Vec<usize> = Vec::new();
+        for consumer in consumers {
+            let recebidos = consumer.join().unwrap();
+            // Cada consumidor recebe os valores de um mesmo produtor em ordem
+            for p in 0..produtores {
+                let do_produtor: Vec<_> = recebidos.iter().filter(|v| **v / por_produtor == p).collect();
+                assert!(do_produtor.windows(2).all(|w| w[0] < w[1]));
+            }
+            todos.extend(recebidos);
+        }
+
+        // Todos os valores chegaram exatamente uma vez
+        todos.sort();
+        assert_eq!(todos, (0..produtores * por_produtor).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+}
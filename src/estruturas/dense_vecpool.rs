@@ -0,0 +1,304 @@
+use std::{iter::Zip, ops::{Index, IndexMut}, slice};
+
+use super::{vecpool::{IndexNode, PoolSlot}, PoolHandle, PoolKey, NULL_INDEX};
+
+/**
+ * Variante do VecPool com os valores contíguos na memória
+ *
+ * No VecPool os valores ficam espalhados no Vec<IndexNode<T>> junto dos espaços vazios, atravessar os valores
+ * precisa pular os vazios e acessa mais memória. Aqui são dois arrays:
+ * - values: Vec<T> com todos os valores ocupados, sempre sem buracos (denso)
+ * - slots: espaços (esparso) que apontam para a posição do valor em values, com a mesma pilha de vazios e gerações do VecPool
+ *
+ * Ao liberar um nó é feito swap_remove em values, o último valor vai para o lugar do removido,
+ * e o espaço que apontava para o último é atualizado (por isso precisa de dense_to_slot).
+ *
+ * Trade-off: atravessar é tão rápido quanto um Vec, mas acessar por handle tem uma indireção a mais.
+ * Mesma ideia do DenseSlotMap (https://docs.rs/slotmap/latest/slotmap/dense/struct.DenseSlotMap.html)
+ */
+pub struct DenseVecPool<T> {
+    values: Vec<T>,
+    // para cada valor em values, qual o espaço que aponta para ele
+    dense_to_slot: Vec<usize>,
+    // Filled(índice em values) ou Empty(próximo vazio)
+    slots: Vec<PoolSlot<usize>>,
+    last_empty: usize,
+    first_generation: u32
+}
+
+impl<T> DenseVecPool<T> {
+    pub fn new() -> DenseVecPool<T> {
+        DenseVecPool {
+            values: Vec::new(),
+            dense_to_slot: Vec::new(),
+            slots: Vec::new(),
+            last_empty: NULL_INDEX,
+            first_generation: 0
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> DenseVecPool<T> {
+        DenseVecPool {
+            values: Vec::with_capacity(capacity),
+            dense_to_slot: Vec::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            last_empty: NULL_INDEX,
+            first_generation: 0
+        }
+    }
+
+    /**
+     * Aloca um novo nó, aproveitando espaços vazios se possível
+     *
+     * O valor sempre vai para o final de values, retorna o handle (índice do espaço + geração)
+     */
+    pub fn alloc_node(&mut self, value: T) -> PoolHandle {
+        let dense_index = self.values.len();
+        let slot_index = if self.last_empty == NULL_INDEX {
+            self.slots.push(PoolSlot { generation: self.first_generation, node: IndexNode::Filled(dense_index) });
+            self.slots.len()-1
+        } else {
+            // fazer pop() da pilha de valores vazios
+            let free_index = self.last_empty;
+            let free_slot = &mut self.slots[free_index];
+            if let IndexNode::Empty(next) = free_slot.node {
+                self.last_empty = next;
+                free_slot.node = IndexNode::Filled(dense_index);
+                free_index
+            } else {
+                panic!("NUNCA DEVERIA OCORRER: Ao obter o last_empty obteve um Filled");
+            }
+        };
+
+        self.values.push(value);
+        self.dense_to_slot.push(slot_index);
+
+        return PoolHandle::new(slot_index, self.slots[slot_index].generation);
+    }
+
+    /**
+     * Libera um nó e retorna o valor que estava nele
+     * O último valor de values é movido para o lugar do removido (swap_remove)
+     */
+    pub fn free_node<K: PoolKey>(&mut self, node: K) -> Option<T> {
+        let slot_index = node.index();
+        let dense_index = self.dense_index(node)?;
+
+        // push() na pilha de valores vazios
+        let slot = &mut self.slots[slot_index];
+        slot.node = IndexNode::Empty(self.last_empty);
+        slot.generation = slot.generation.wrapping_add(1);
+        self.last_empty = slot_index;
+
+        let value = self.values.swap_remove(dense_index);
+        self.dense_to_slot.swap_remove(dense_index);
+        // Se não era o último, o espaço do valor que foi movido precisa apontar para a nova posição
+        if dense_index < self.values.len() {
+            let moved_slot = self.dense_to_slot[dense_index];
+            self.slots[moved_slot].node = IndexNode::Filled(dense_index);
+        }
+
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        // Os espaços criados depois do clear devem ter geração maior que qualquer handle já retornado
+        for slot in self.slots.iter() {
+            let next_generation = slot.generation.wrapping_add(1);
+            if next_generation > self.first_generation {
+                self.first_generation = next_generation;
+            }
+        }
+
+        self.values.clear();
+        self.dense_to_slot.clear();
+        self.slots.clear();
+        self.last_empty = NULL_INDEX;
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn contains<K: PoolKey>(&self, node: K) -> bool {
+        self.dense_index(node).is_some()
+    }
+
+    /**
+     * Posição do valor em values, se o handle ainda for válido
+     */
+    fn dense_index<K: PoolKey>(&self, node: K) -> Option<usize> {
+        match self.slots.get(node.index()) {
+            Some(PoolSlot { generation, node: IndexNode::Filled(dense_index) }) if node.matches(*generation) => Some(*dense_index),
+            _ => None
+        }
+    }
+
+    pub fn get_node<K: PoolKey>(&self, node: K) -> Option<&T> {
+        let dense_index = self.dense_index(node)?;
+        Some(&self.values[dense_index])
+    }
+
+    pub fn get_mut_node<K: PoolKey>(&mut self, node: K) -> Option<&mut T> {
+        let dense_index = self.dense_index(node)?;
+        Some(&mut self.values[dense_index])
+    }
+
+    /**
+     * Valores ocupados, contíguos. A ordem muda conforme os nós são liberados
+     */
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = PoolHandle> + '_ {
+        self.dense_to_slot.iter().map(|&slot_index| PoolHandle::new(slot_index, self.slots[slot_index].generation))
+    }
+
+    pub fn iter(&self) -> DenseVecPoolIter<'_, T> {
+        DenseVecPoolIter {
+            slots: &self.slots,
+            inner: self.dense_to_slot.iter().zip(self.values.iter())
+        }
+    }
+}
+
+// let value = pool[handle];
+impl<T, K: PoolKey> Index<K> for DenseVecPool<T> {
+    type Output = T;
+
+    fn index(&self, node: K) -> &Self::Output {
+        match self.get_node(node) {
+            Some(value) => value,
+            None => panic!("Acessou um Empty ou handle de geração antiga {:?}", node.index()),
+        }
+    }
+}
+
+// pool[handle] = value;
+impl<T, K: PoolKey> IndexMut<K> for DenseVecPool<T> {
+    fn index_mut(&mut self, node: K) -> &mut Self::Output {
+        match self.dense_index(node) {
+            Some(dense_index) => &mut self.values[dense_index],
+            None => panic!("Acessou um Empty ou handle de geração antiga {:?}", node.index()),
+        }
+    }
+}
+
+impl<T: Clone> Clone for DenseVecPool<T> {
+    fn clone(&self) -> Self {
+        DenseVecPool {
+            values: self.values.clone(),
+            dense_to_slot: self.dense_to_slot.clone(),
+            slots: self.slots.clone(),
+            last_empty: self.last_empty,
+            first_generation: self.first_generation
+        }
+    }
+}
+
+pub struct DenseVecPoolIter<'a, T> {
+    slots: &'a [PoolSlot<usize>],
+    inner: Zip<slice::Iter<'a, usize>, slice::Iter<'a, T>>
+}
+
+impl<'a, T> Iterator for DenseVecPoolIter<'a, T> {
+    type Item = (PoolHandle, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&slot_index, value) = self.inner.next()?;
+        Some((PoolHandle::new(slot_index, self.slots[slot_index].generation), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for DenseVecPoolIter<'_, T> {}
+
+// for (handle, value) in &pool
+impl<'a, T> IntoIterator for &'a DenseVecPool<T> {
+    type Item = (PoolHandle, &'a T);
+    type IntoIter = DenseVecPoolIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DenseVecPool;
+
+    #[test]
+    pub fn dense_alloc_logic() {
+        let mut pool = DenseVecPool::new();
+        let a = pool.alloc_node('A');
+        let b = pool.alloc_node('B');
+        let c = pool.alloc_node('C');
+        let d = pool.alloc_node('D');
+        assert_eq!(pool.len(), 4);
+        assert_eq!(pool.values(), &['A','B','C','D']);
+
+        // swap_remove: D vai para o lugar de B
+        assert_eq!(pool.free_node(b), Some('B'));
+        assert_eq!(pool.values(), &['A','D','C']);
+        assert_eq!(pool.get_node(d), Some(&'D'));
+        assert_eq!(pool.get_node(b), None);
+        assert_eq!(pool.free_node(b), None);
+
+        // Reaproveita o espaço de B, com outra geração
+        let e = pool.alloc_node('E');
+        assert_eq!(e.index(), b.index());
+        assert_eq!(pool.get_node(b), None);
+        assert_eq!(pool.get_node(e), Some(&'E'));
+        assert_eq!(pool.values(), &['A','D','C','E']);
+
+        // Remover o último não move ninguém
+        assert_eq!(pool.free_node(e), Some('E'));
+        assert_eq!(pool.values(), &['A','D','C']);
+
+        *pool.get_mut_node(a).unwrap() = 'a';
+        pool[c] = 'c';
+        assert_eq!(pool[a], 'a');
+        assert_eq!(pool.values(), &['a','D','c']);
+
+        let mut keys: Vec<_> = pool.keys().collect();
+        keys.sort_by_key(|k| k.index());
+        assert_eq!(keys, vec![a, c, d]);
+        for (handle, value) in &pool {
+            assert_eq!(pool.get_node(handle), Some(value));
+        }
+
+        pool.clear();
+        assert_eq!(pool.len(), 0);
+        let f = pool.alloc_node('F');
+        assert_eq!(f.index(), a.index());
+        assert_eq!(pool.get_node(a), None);
+        assert_eq!(pool.get_node(f), Some(&'F'));
+    }
+
+    #[test]
+    pub fn dense_many() {
+        let mut pool = DenseVecPool::with_capacity(100);
+        let mut handles: Vec<_> = (0..100).map(|i| pool.alloc_node(i)).collect();
+
+        // Remove os múltiplos de 3
+        for i in (0..100).filter(|i| i % 3 == 0) {
+            assert_eq!(pool.free_node(handles[i]), Some(i));
+        }
+        handles.retain(|h| pool.contains(*h));
+        assert_eq!(pool.len(), 66);
+        for h in handles.iter() {
+            let value = pool[*h];
+            assert_ne!(value % 3, 0);
+        }
+
+        let mut values = pool.values().to_vec();
+        values.sort();
+        assert_eq!(values, (0..100).filter(|i| i % 3 != 0).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,382 @@
+use std::{fmt, ops::{Index, IndexMut}};
+
+use super::Queue;
+
+/**
+ * Fila com duas pontas (double-ended queue), insere e remove tanto do início quanto do final
+ * Toda Deque também é uma Queue (enqueue no final, dequeue do início), len e is_empty vêm de Queue
+ */
+pub trait Deque<T>: Queue<T> {
+    /**
+     * Insere um elemento no início
+     */
+    fn push_front(&mut self, value: T);
+
+    /**
+     * Insere um elemento no final
+     */
+    fn push_back(&mut self, value: T);
+
+    /**
+     * Remove um elemento do início
+     */
+    fn pop_front(&mut self) -> Option<T>;
+
+    /**
+     * Remove um elemento do final
+     */
+    fn pop_back(&mut self) -> Option<T>;
+
+    /**
+     * 'Empresta' o valor do início
+     */
+    fn front(&self) -> Option<&T>;
+
+    /**
+     * 'Empresta' o valor do final
+     */
+    fn back(&self) -> Option<&T>;
+}
+
+/**
+ * Deque em um buffer circular que cresce conforme necessário (como o VecDeque)
+ *
+ * Funcionamento:
+ * - buf: Vec<Option<T>> com capacidade fixa, None nos espaços não usados (para não precisar de unsafe)
+ * - head: posição do primeiro valor, os próximos seguem em (head + i) % capacidade, dando a volta no final
+ * - push_front anda head para trás, push_back escreve depois do último, ambos O(1)
+ *
+ * Quando enche, um novo buffer com o dobro do tamanho é criado e os valores são copiados em ordem (head volta a 0)
+ */
+pub struct RingDeque<T> {
+    buf: Vec<Option<T>>,
+    head: usize,
+    length: usize
+}
+
+impl<T> RingDeque<T> {
+    pub fn new() -> RingDeque<T> {
+        RingDeque::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> RingDeque<T> {
+        let mut buf = Vec::new();
+        buf.resize_with(capacity, || None);
+        RingDeque { buf: buf, head: 0, length: 0 }
+    }
+
+    pub fn from<const N: usize>(values: [T; N]) -> RingDeque<T> {
+        let mut ret = RingDeque::with_capacity(N);
+        for value in values.into_iter() {
+            ret.push_back(value);
+        }
+        return ret;
+    }
+
+    /**
+     * Tamanho do buffer alocado (cresce sozinho). Não é um limite: Queue::capacity() é sempre None para o RingDeque
+     */
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn clear(&mut self) {
+        for value in self.buf.iter_mut() {
+            *value = None;
+        }
+        self.head = 0;
+        self.length = 0;
+    }
+
+    /**
+     * Posição no buffer do i-ésimo valor
+     */
+    fn physical(&self, i: usize) -> usize {
+        (self.head + i) % self.buf.len()
+    }
+
+    /**
+     * i-ésimo valor a partir do início
+     */
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.length { return None; }
+        return self.buf[self.physical(i)].as_ref();
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.length { return None; }
+        let index = self.physical(i);
+        return self.buf[index].as_mut();
+    }
+
+    pub fn iter(&self) -> RingDequeIter<'_, T> {
+        RingDequeIter { deque: self, front: 0, back: self.length }
+    }
+
+    /**
+     * Garante espaço para mais um valor, dobrando a capacidade se estiver cheio
+     */
+    fn grow(&mut self) {
+        if self.length < self.buf.len() { return; }
+
+        let new_capacity = if self.buf.is_empty() { 4 } else { self.buf.len() * 2 };
+        let mut new_buf: Vec<Option<T>> = Vec::with_capacity(new_capacity);
+        for i in 0..self.length {
+            let index = self.physical(i);
+            new_buf.push(self.buf[index].take());
+        }
+        new_buf.resize_with(new_capacity, || None);
+        self.buf = new_buf;
+        self.head = 0;
+    }
+}
+
+impl<T> Deque<T> for RingDeque<T> {
+    fn push_front(&mut self, value: T) {
+        self.grow();
+        self.head = (self.head + self.buf.len() - 1) % self.buf.len();
+        self.buf[self.head] = Some(value);
+        self.length += 1;
+    }
+
+    fn push_back(&mut self, value: T) {
+        self.grow();
+        let index = self.physical(self.length);
+        self.buf[index] = Some(value);
+        self.length += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 { return None; }
+
+        let value = self.buf[self.head].take();
+        self.head = (self.head + 1) % self.buf.len();
+        self.length -= 1;
+        return value;
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 { return None; }
+
+        let index = self.physical(self.length - 1);
+        self.length -= 1;
+        return self.buf[index].take();
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    fn back(&self) -> Option<&T> {
+        if self.length == 0 { return None; }
+        self.get(self.length - 1)
+    }
+}
+
+impl<T> Queue<T> for RingDeque<T> {
+    fn enqueue(&mut self, value: T) { self.push_back(value); }
+    fn dequeue(&mut self) -> Option<T> { return self.pop_front(); }
+    fn head(&self) -> Option<&T> { return self.front(); }
+    fn tail(&self) -> Option<&T> { return self.back(); }
+    fn len(&self) -> usize { return self.length; }
+}
+
+impl<T> Index<usize> for RingDeque<T> {
+    type Output = T;
+    fn index(&self, i: usize) -> &Self::Output {
+        match self.get(i) {
+            Some(value) => value,
+            None => panic!("Índice {} fora do RingDeque de tamanho {}", i, self.length),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RingDeque<T> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        let length = self.length;
+        match self.get_mut(i) {
+            Some(value) => value,
+            None => panic!("Índice {} fora do RingDeque de tamanho {}", i, length),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RingDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = RingDeque::new();
+        for value in iter {
+            ret.push_back(value);
+        }
+        return ret;
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct RingDequeIter<'a, T> {
+    deque: &'a RingDeque<T>,
+    front: usize,
+    back: usize
+}
+
+impl<'a, T> Iterator for RingDequeIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back { return None; }
+        self.front += 1;
+        self.deque.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<T> DoubleEndedIterator for RingDequeIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back { return None; }
+        self.back -= 1;
+        self.deque.get(self.back)
+    }
+}
+
+impl<T> ExactSizeIterator for RingDequeIter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a RingDeque<T> {
+    type Item = &'a T;
+    type IntoIter = RingDequeIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub fn run_deque_tests<T: Deque<char>>(deque: &mut T) {
+    for iter in 0..3 {
+        assert_eq!(deque.front(), None);
+        assert_eq!(deque.back(), None);
+        assert!(deque.is_empty());
+
+        deque.push_back('C');
+        deque.push_front('B');
+        deque.push_back('D');
+        deque.push_front('A');
+        deque.push_back('E');
+
+        assert_eq!(deque.len(), 5);
+        assert_eq!(deque.front(), Some(&'A'));
+        assert_eq!(deque.back(), Some(&'E'));
+
+        assert_eq!(deque.pop_front(), Some('A'));
+        assert_eq!(deque.pop_back(), Some('E'));
+        deque.push_front('0');
+        deque.push_back('F');
+        assert_eq!(deque.len(), 5);
+        assert_eq!(deque.pop_back(), Some('F'));
+        assert_eq!(deque.pop_front(), Some('0'));
+        assert_eq!(deque.pop_front(), Some('B'));
+        assert_eq!(deque.pop_back(), Some('D'));
+        assert_eq!(deque.front(), deque.back());
+        assert_eq!(deque.pop_back(), Some('C'));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.pop_front(), None);
+    }
+
+    // Usando como pilha dos dois lados
+    for c in ['A','B','C','D','E','F','G','H','I','J'] {
+        deque.push_front(c);
+    }
+    for c in ['J','I','H','G','F','E','D','C','B','A'] {
+        assert_eq!(deque.pop_front(), Some(c));
+    }
+    for c in ['A','B','C','D','E','F','G','H','I','J'] {
+        deque.push_back(c);
+    }
+    for c in ['J','I','H','G','F','E','D','C','B','A'] {
+        assert_eq!(deque.pop_back(), Some(c));
+    }
+    assert_eq!(deque.len(), 0);
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::estruturas::{run_queue_tests, Deque, Queue};
+
+    use super::{run_deque_tests, RingDeque};
+
+    #[test]
+    pub fn ring_deque() {
+        let mut deque = RingDeque::new();
+        run_deque_tests(&mut deque);
+        run_queue_tests(&mut deque);
+    }
+
+    #[test]
+    pub fn ring_deque_wrap() {
+        let mut deque = RingDeque::with_capacity(4);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+        // Agora o final dá a volta no buffer
+        deque.push_back(4);
+        deque.push_back(5);
+        deque.push_front(0);
+        assert_eq!(deque.buffer_capacity(), 4);
+        assert_eq!(Queue::capacity(&deque), None);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![0, 3, 4, 5]);
+
+        // Cresce mantendo a ordem
+        deque.push_front(-1);
+        assert_eq!(deque.buffer_capacity(), 8);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 3, 4, 5]);
+        assert_eq!(deque.iter().rev().copied().collect::<Vec<_>>(), vec![5, 4, 3, 0, -1]);
+        assert_eq!(deque[2], 3);
+        deque[2] = 30;
+        assert_eq!(deque.get(2), Some(&30));
+        assert_eq!(deque.get(5), None);
+        assert_eq!(format!("{:?}", deque), "[-1, 0, 30, 4, 5]");
+
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.buffer_capacity(), 8);
+
+        let deque: RingDeque<i32> = (0..100).collect();
+        assert_eq!(deque.len(), 100);
+        assert!(deque.iter().copied().eq(0..100));
+    }
+
+    #[test]
+    pub fn ring_deque_model() {
+        // Compara com o VecDeque em uma sequência pseudo-aleatória de operações
+        let mut deque = RingDeque::new();
+        let mut model = std::collections::VecDeque::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        for i in 0..2000 {
+            match rng.random_range(0..5) {
+                0 => { deque.push_front(i); model.push_front(i); },
+                1 | 2 => { deque.push_back(i); model.push_back(i); },
+                3 => assert_eq!(deque.pop_front(), model.pop_front()),
+                _ => assert_eq!(deque.pop_back(), model.pop_back()),
+            }
+            assert_eq!(deque.len(), model.len());
+            assert_eq!(deque.front(), model.front());
+            assert_eq!(deque.back(), model.back());
+        }
+        assert!(deque.iter().eq(model.iter()));
+    }
+}
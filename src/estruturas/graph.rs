@@ -1,16 +1,481 @@
-use std::{collections::{HashSet, VecDeque}, hash::Hash, marker::PhantomData};
+use std::{collections::{HashMap, HashSet, VecDeque}, hash::Hash, marker::PhantomData, ops::Add};
 
-use super::{LinkedStack, Stack, VecPool, NULL_INDEX};
+use super::{LinkedStack, PriorityQueue, Stack, VecPool, VisitedSet, NULL_INDEX};
 
 pub trait GraphIterState<T> {
     fn push_neighbor(&mut self, neighbor: T);
 }
 
-pub struct GraphIter<T> 
+/**
+ * Igual ao GraphIterState, mas cada vizinho vem com o custo da aresta até ele (Dijkstra, A*)
+ */
+pub trait GraphCostState<T, C> {
+    fn push_neighbor(&mut self, neighbor: T, cost: C);
+}
+
+type Heuristic<T, C> = Box<dyn Fn(&T) -> C>;
+
+/**
+ * Ordem em que os vértices a explorar saem da fronteira
+ */
+enum SearchOrder {
+    // Último que entrou sai primeiro (também no depth_limited e iterative_deepening)
+    DepthFirst,
+    // Primeiro que entrou sai primeiro (também no bidirectional)
+    BreadthFirst,
+    // Menor custo acumulado primeiro (+ heurística no A*)
+    Cheapest,
+    // Menor heurística primeiro, ignorando o custo até aqui
+    Greedy
+}
+
+/**
+ * O que o GraphIter/GraphSearch guarda junto de cada vértice a explorar:
+ * - (): GraphIter, nada
+ * - LinkedStack<T>: GraphSearch, o caminho inteiro até o vértice (compartilhando o começo com os outros caminhos)
+ * - ParentLink<T>: GraphSearch::with_parent_map, só o vértice anterior, o caminho é montado no final pelo mapa de pais
+ */
+pub trait PathRecord<T>: Clone {
+    /**
+     * Se true, o GraphSearch anota o pai de cada vértice visitado (e usa o mapa de pais como lista de visitados)
+     */
+    const PARENTS: bool = false;
+
+    fn start(node: &T) -> Self;
+    fn child(&self, node: &T) -> Self;
+    /**
+     * Busca bidirecional: junta o que veio do início com o que veio do destino, as duas terminando no mesmo vértice
+     */
+    fn join(forward: &Self, backward: &Self, goal: &T) -> Self;
+    /**
+     * Vértice anterior, guardado no mapa de pais ao visitar
+     */
+    fn parent(&self) -> Option<&T> {
+        None
+    }
+}
+
+impl<T> PathRecord<T> for () {
+    fn start(_: &T) -> Self {}
+    fn child(&self, _: &T) -> Self {}
+    fn join(_: &Self, _: &Self, _: &T) -> Self {}
+}
+
+impl<T: Clone> PathRecord<T> for LinkedStack<T> {
+    fn start(node: &T) -> Self {
+        let mut path = LinkedStack::new();
+        path.push(node.clone());
+        return path;
+    }
+
+    fn child(&self, node: &T) -> Self {
+        let mut path = self.clone();
+        path.push(node.clone());
+        return path;
+    }
+
+    fn join(forward: &Self, backward: &Self, _: &T) -> Self {
+        // backward tem o vértice do encontro no topo e o destino no fundo
+        let mut path = forward.clone();
+        for node in backward.iter().skip(1) {
+            path.push(node.clone());
+        }
+        return path;
+    }
+}
+
+/**
+ * Vértice com o anterior dele no caminho, o que o GraphSearch::next() retorna no modo with_parent_map
+ * peek() funciona igual ao do LinkedStack (retorna o vértice), então o mesmo código de visita serve nos dois modos
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParentLink<T> {
+    node: T,
+    parent: Option<T>
+}
+
+impl<T> ParentLink<T> {
+    pub fn peek(&self) -> Option<&T> {
+        Some(&self.node)
+    }
+
+    pub fn node(&self) -> &T {
+        &self.node
+    }
+
+    /**
+     * None no vértice inicial
+     */
+    pub fn parent(&self) -> Option<&T> {
+        self.parent.as_ref()
+    }
+}
+
+impl<T: Clone> PathRecord<T> for ParentLink<T> {
+    const PARENTS: bool = true;
+
+    fn start(node: &T) -> Self {
+        ParentLink { node: node.clone(), parent: None }
+    }
+
+    fn child(&self, node: &T) -> Self {
+        ParentLink { node: node.clone(), parent: Some(self.node.clone()) }
+    }
+
+    fn join(_: &Self, _: &Self, goal: &T) -> Self {
+        // O caminho é montado pelo get_path, com os mapas de pais de cada lado
+        ParentLink { node: goal.clone(), parent: None }
+    }
+
+    fn parent(&self) -> Option<&T> {
+        self.parent.as_ref()
+    }
+}
+
+struct SearchEntry<T, X, C> {
+    node: T,
+    extra: X,
+    cost: C,
+    // Quantidade de arestas desde o início (ou desde o destino no bidirecional)
+    depth: usize,
+    // Bidirecional: veio da busca a partir do destino
+    backward: bool
+}
+
+/**
+ * Limite de profundidade, no iterative_deepening aumenta de 1 em 1 até max_depth recomeçando a busca
+ */
+struct Deepening<T> {
+    start: T,
+    first_limit: usize,
+    limit: usize,
+    max_depth: usize,
+    // Algum vizinho deixou de ser explorado por causa do limite, então vale a pena aumentar
+    cut_off: bool,
+    // Menor profundidade em que cada vértice já foi visitado nesta rodada,
+    // só visita de novo se chegar por um caminho mais curto
+    depths: HashMap<T, usize>
+}
+
+/**
+ * Busca bidirecional: o que cada lado já visitou, para juntar quando um lado chegar em um vértice do outro
+ */
+struct Bidirectional<T, X, C> {
+    goal: T,
+    // Vértice -> (extra, profundidade, custo) de quando foi visitado
+    forward: HashMap<T, (X, usize, C)>,
+    backward: HashMap<T, (X, usize, C)>,
+    meeting_point: Option<T>
+}
+
+/**
+ * Parte comum do GraphIter e GraphSearch: a fronteira (quem falta explorar) conforme a estratégia, e os visitados
+ */
+struct SearchCore<T, X, C, V = HashSet<T>> {
+    order: SearchOrder,
+    heuristic: Option<Heuristic<T, C>>,
+    visited: V,
+    to_explore: VecDeque<SearchEntry<T, X, C>>,
+    // Usado no lugar de to_explore no Cheapest e Greedy
+    to_explore_priority: PriorityQueue<SearchEntry<T, X, C>, C>,
+    deepening: Option<Deepening<T>>,
+    bidirectional: Option<Bidirectional<T, X, C>>,
+    // Vértice -> anterior no caminho (None no início), somente quando X::PARENTS (ParentLink)
+    parents: HashMap<T, Option<T>>,
+    current: Option<SearchEntry<T, X, C>>
+}
+
+impl<T, X, C> SearchCore<T, X, C> 
+where
+    T: Clone + Eq + Hash,
+    X: PathRecord<T>,
+    C: Copy + Ord + Add<Output = C> + Default
 {
-    visited: HashSet<T>,
-    to_explore: VecDeque<T>,
-    depth_first: bool
+    fn new(order: SearchOrder, start: T, heuristic: Option<Heuristic<T, C>>) -> Self {
+        let mut ret = SearchCore {
+            order: order,
+            heuristic: heuristic,
+            visited: HashSet::new(),
+            to_explore: VecDeque::new(),
+            to_explore_priority: PriorityQueue::new(),
+            deepening: None,
+            bidirectional: None,
+            parents: HashMap::new(),
+            current: None
+        };
+        ret.push_start(start, false);
+        return ret;
+    }
+
+    /**
+     * Troca o que é guardado junto de cada vértice, somente antes de começar (a fronteira só tem os inícios)
+     */
+    fn convert<Y: PathRecord<T>>(mut self) -> SearchCore<T, Y, C> {
+        assert!(self.current.is_none(), "Deve ser chamado antes do primeiro next()");
+
+        let mut starts = Vec::new();
+        while let Some(entry) = self.pop() {
+            starts.push((entry.node, entry.backward));
+        }
+        if let SearchOrder::DepthFirst = self.order {
+            starts.reverse();
+        }
+
+        let mut ret = SearchCore {
+            order: self.order,
+            heuristic: self.heuristic,
+            visited: HashSet::new(),
+            to_explore: VecDeque::new(),
+            to_explore_priority: PriorityQueue::new(),
+            deepening: self.deepening,
+            bidirectional: self.bidirectional.map(|bidirectional| Bidirectional {
+                goal: bidirectional.goal, forward: HashMap::new(), backward: HashMap::new(), meeting_point: None
+            }),
+            parents: HashMap::new(),
+            current: None
+        };
+        for (node, backward) in starts {
+            ret.push_start(node, backward);
+        }
+        return ret;
+    }
+
+    fn with_deepening(start: T, first_limit: usize, max_depth: usize) -> Self {
+        let mut ret = Self::new(SearchOrder::DepthFirst, start.clone(), None);
+        ret.deepening = Some(Deepening {
+            start: start, first_limit: first_limit, limit: first_limit, max_depth: max_depth, cut_off: false, depths: HashMap::new()
+        });
+        return ret;
+    }
+
+    fn with_bidirectional(start: T, goal: T) -> Self {
+        let mut ret = Self::new(SearchOrder::BreadthFirst, start, None);
+        ret.bidirectional = Some(Bidirectional { goal: goal.clone(), forward: HashMap::new(), backward: HashMap::new(), meeting_point: None });
+        ret.push_start(goal, true);
+        return ret;
+    }
+
+    /**
+     * Troca a lista de visitados, somente antes de começar
+     */
+    fn with_visited<W: VisitedSet<T>>(self, visited: W) -> SearchCore<T, X, C, W> {
+        assert!(self.current.is_none(), "Deve ser chamado antes do primeiro next()");
+
+        SearchCore {
+            order: self.order,
+            heuristic: self.heuristic,
+            visited: visited,
+            to_explore: self.to_explore,
+            to_explore_priority: self.to_explore_priority,
+            deepening: self.deepening,
+            bidirectional: self.bidirectional,
+            parents: self.parents,
+            current: self.current
+        }
+    }
+}
+
+impl<T, X, C, V> SearchCore<T, X, C, V> 
+where
+    T: Clone + Eq + Hash,
+    X: PathRecord<T>,
+    C: Copy + Ord + Add<Output = C> + Default,
+    V: VisitedSet<T>
+{
+
+    fn push_start(&mut self, start: T, backward: bool) {
+        let extra = X::start(&start);
+        self.push(SearchEntry { node: start, extra: extra, cost: C::default(), depth: 0, backward: backward });
+    }
+
+    fn push(&mut self, entry: SearchEntry<T, X, C>) {
+        match self.order {
+            SearchOrder::DepthFirst | SearchOrder::BreadthFirst => self.to_explore.push_back(entry),
+            SearchOrder::Cheapest => {
+                let priority = match &self.heuristic {
+                    Some(heuristic) => entry.cost + heuristic(&entry.node),
+                    None => entry.cost,
+                };
+                self.to_explore_priority.push(entry, priority);
+            },
+            SearchOrder::Greedy => {
+                let priority = (self.heuristic.as_ref().unwrap())(&entry.node);
+                self.to_explore_priority.push(entry, priority);
+            },
+        }
+    }
+
+    fn pop(&mut self) -> Option<SearchEntry<T, X, C>> {
+        match self.order {
+            SearchOrder::DepthFirst => self.to_explore.pop_back(),
+            SearchOrder::BreadthFirst => self.to_explore.pop_front(),
+            SearchOrder::Cheapest | SearchOrder::Greedy => self.to_explore_priority.pop().map(|(entry, _)| entry),
+        }
+    }
+
+    /**
+     * Volta ao estado inicial a partir de start (mantendo o destino no bidirecional), sem liberar a memória já alocada
+     */
+    fn reset(&mut self, start: T) {
+        self.visited.clear();
+        self.to_explore.clear();
+        self.to_explore_priority.clear();
+        self.parents.clear();
+        self.current = None;
+        if let Some(deepening) = self.deepening.as_mut() {
+            deepening.start = start.clone();
+            deepening.limit = deepening.first_limit;
+            deepening.cut_off = false;
+            deepening.depths.clear();
+        }
+
+        self.push_start(start, false);
+        if let Some(bidirectional) = self.bidirectional.as_mut() {
+            bidirectional.forward.clear();
+            bidirectional.backward.clear();
+            bidirectional.meeting_point = None;
+            let goal = bidirectional.goal.clone();
+            self.push_start(goal, true);
+        }
+    }
+
+    /**
+     * Acabaram os vértices: no iterative_deepening recomeça com o limite maior, se algum vértice ficou de fora
+     */
+    fn restart(&mut self) -> bool {
+        let Some(deepening) = self.deepening.as_mut() else { return false; };
+        if !deepening.cut_off || deepening.limit >= deepening.max_depth {
+            return false;
+        }
+
+        deepening.limit += 1;
+        deepening.cut_off = false;
+        deepening.depths.clear();
+        self.parents.clear();
+        let start = deepening.start.clone();
+        self.push_start(start, false);
+        return true;
+    }
+
+    fn next(&mut self) -> Option<&SearchEntry<T, X, C>> {
+        loop {
+            let Some(entry) = self.pop() else {
+                if self.restart() { continue; }
+                return None;
+            };
+
+            if let Some(deepening) = self.deepening.as_mut() {
+                if deepening.depths.get(&entry.node).is_some_and(|depth| *depth <= entry.depth) {
+                    continue;
+                }
+                deepening.depths.insert(entry.node.clone(), entry.depth);
+            } else if let Some(bidirectional) = self.bidirectional.as_mut() {
+                if bidirectional.meeting_point.is_some() {
+                    return None;
+                }
+
+                let (mine, other) = if entry.backward {
+                    (&mut bidirectional.backward, &bidirectional.forward)
+                } else {
+                    (&mut bidirectional.forward, &bidirectional.backward)
+                };
+                if mine.contains_key(&entry.node) {
+                    continue;
+                }
+
+                // Encontrou o outro lado: retorna o caminho inteiro, terminando no destino
+                if let Some((other_extra, other_depth, other_cost)) = other.get(&entry.node) {
+                    let (forward, backward) = if entry.backward { (other_extra, &entry.extra) } else { (&entry.extra, other_extra) };
+                    let extra = X::join(forward, backward, &bidirectional.goal);
+                    // Guarda também deste lado, para montar o caminho pelos mapas de pais
+                    mine.insert(entry.node.clone(), (entry.extra.clone(), entry.depth, entry.cost));
+                    bidirectional.meeting_point = Some(entry.node);
+                    self.current = Some(SearchEntry {
+                        node: bidirectional.goal.clone(),
+                        extra: extra,
+                        cost: entry.cost + *other_cost,
+                        depth: entry.depth + other_depth,
+                        backward: false
+                    });
+                    return self.current.as_ref();
+                }
+                mine.insert(entry.node.clone(), (entry.extra.clone(), entry.depth, entry.cost));
+            } else if X::PARENTS {
+                // O mapa de pais também serve de lista de visitados
+                if self.parents.contains_key(&entry.node) {
+                    continue;
+                }
+            } else if !self.visited.insert(entry.node.clone()) {
+                // Insere na lista, e se já está visitado deve pular este nó
+                continue;
+            }
+
+            if X::PARENTS && self.bidirectional.is_none() {
+                self.parents.insert(entry.node.clone(), entry.extra.parent().cloned());
+            }
+            self.current = Some(entry);
+            return self.current.as_ref();
+        }
+    }
+
+    fn push_neighbor(&mut self, neighbor: T, cost: C) {
+        let current = self.current.as_ref().unwrap();
+        let depth = current.depth + 1;
+
+        if let Some(deepening) = self.deepening.as_mut() {
+            if depth > deepening.limit {
+                deepening.cut_off = true;
+                return;
+            }
+            if deepening.depths.get(&neighbor).is_some_and(|visited| *visited <= depth) {
+                return;
+            }
+        } else if let Some(bidirectional) = self.bidirectional.as_ref() {
+            let mine = if current.backward { &bidirectional.backward } else { &bidirectional.forward };
+            if mine.contains_key(&neighbor) {
+                return;
+            }
+        } else if X::PARENTS {
+            if self.parents.contains_key(&neighbor) {
+                return;
+            }
+        } else if self.visited.contains(&neighbor) {
+            return;
+        }
+
+        let entry = SearchEntry {
+            extra: current.extra.child(&neighbor),
+            node: neighbor,
+            cost: current.cost + cost,
+            depth: depth,
+            backward: current.backward
+        };
+        self.push(entry);
+    }
+}
+
+/**
+ * Percorre um grafo implícito: next() retorna o próximo vértice, e quem usa informa os vizinhos dele com push_neighbor
+ * 
+ * Estratégias:
+ * - depth_first / breadth_first
+ * - greedy_best_first: sai primeiro o vértice com menor heurística (ex: mais perto do destino)
+ * - depth_limited: em profundidade, sem passar de limit arestas desde o início
+ * - iterative_deepening: depth_limited com limite 0, 1, 2... até max_depth, recomeçando do início a cada rodada
+ *   (os vértices se repetem entre as rodadas, e um vértice pode ser visitado de novo se chegar por um caminho mais curto)
+ * - bidirectional: em largura a partir do início e do destino ao mesmo tempo, os vizinhos informados precisam valer
+ *   nos dois sentidos (grafo não direcionado). Quando os dois lados se encontram retorna o destino e termina
+ *   (o destino também sai logo no início, como começo da busca de trás para frente, então continuar até next() retornar None)
+ * 
+ * Visitados (V): HashSet<T> por padrão, em grades é mais rápido usar um Vec2D<bool> do tamanho da grade:
+ * GraphIter::breadth_first((x, y)).with_visited(Vec2D::new(w, h, false))
+ * (depth_limited, iterative_deepening e bidirectional guardam a profundidade de cada vértice em HashMap, não usam V)
+ * 
+ * reset(start) recomeça a busca reaproveitando a memória, para usar o mesmo GraphIter a cada frame
+ */
+pub struct GraphIter<T, C = usize, V = HashSet<T>> 
+{
+    core: SearchCore<T, (), C, V>
 }
 
 impl<T> GraphIter<T> 
@@ -26,53 +491,113 @@ where
     }
 
     fn new(depth_first: bool, start: T) -> Self {
-        let mut to_explore = VecDeque::new();
-        to_explore.push_back(start);
-
+        let order = if depth_first { SearchOrder::DepthFirst } else { SearchOrder::BreadthFirst };
         Self {
-            visited: HashSet::new(),
-            to_explore: to_explore,
-            depth_first: depth_first
+            core: SearchCore::new(order, start, None)
         }
     }
 
+    pub fn depth_limited(start: T, limit: usize) -> Self {
+        Self { core: SearchCore::with_deepening(start, limit, limit) }
+    }
+
+    pub fn iterative_deepening(start: T, max_depth: usize) -> Self {
+        Self { core: SearchCore::with_deepening(start, 0, max_depth) }
+    }
+
+    pub fn bidirectional(start: T, goal: T) -> Self {
+        Self { core: SearchCore::with_bidirectional(start, goal) }
+    }
+}
+
+impl<T, C> GraphIter<T, C> 
+where
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default
+{
+    /**
+     * heuristic: estimativa do quanto falta até o destino, menor sai primeiro
+     */
+    pub fn greedy_best_first<H: Fn(&T) -> C + 'static>(start: T, heuristic: H) -> Self {
+        Self { core: SearchCore::new(SearchOrder::Greedy, start, Some(Box::new(heuristic))) }
+    }
+
+    /**
+     * Troca a lista de visitados, somente antes do primeiro next(). O que já estiver marcado nela não será visitado
+     */
+    pub fn with_visited<W: VisitedSet<T>>(self, visited: W) -> GraphIter<T, C, W> {
+        GraphIter { core: self.core.with_visited(visited) }
+    }
+}
+
+impl<T, C, V> GraphIter<T, C, V> 
+where
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    V: VisitedSet<T>
+{
     pub fn next(&mut self) -> Option<T> {
-        while let Some(node_index) = if self.depth_first { 
-            self.to_explore.pop_back() 
-        } else { 
-            self.to_explore.pop_front() 
-        } {
-            // Insere na lista, e se já está visitado deve pular este nó
-            if !self.visited.insert(node_index.clone()) {
-                continue;
-            }
+        return self.core.next().map(|entry| entry.node.clone());
+    }
 
-            return Some(node_index);
-        }
-        return None;
+    /**
+     * Quantidade de arestas desde o início até o vértice retornado pelo último next()
+     */
+    pub fn depth(&self) -> usize {
+        return self.core.current.as_ref().unwrap().depth;
+    }
+
+    /**
+     * Bidirecional: vértice onde as buscas do início e do destino se encontraram
+     */
+    pub fn meeting_point(&self) -> Option<&T> {
+        return self.core.bidirectional.as_ref()?.meeting_point.as_ref();
+    }
+
+    /**
+     * Recomeça a busca a partir de start, com a mesma estratégia, limpando os visitados sem liberar a memória
+     */
+    pub fn reset(&mut self, start: T) {
+        self.core.reset(start);
     }
 }
 
-impl<T> GraphIterState<T> for GraphIter<T> 
+impl<T, C, V> GraphIterState<T> for GraphIter<T, C, V> 
 where
-    T: Clone + Eq + Hash
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    V: VisitedSet<T>
 {  
     fn push_neighbor(&mut self, neighbor: T) {
-        if !self.visited.contains(&neighbor) {
-            self.to_explore.push_back(neighbor);
-        }
+        self.core.push_neighbor(neighbor, C::default());
     }
 }
 
-// A FAZER: medir se faz sentido substituir no to_explore Vec<T> por um tipo de push-only immutable Single Linked List, que seria leve de 'copiar'
-// Já que não seriam cópias reais e sim referências ao resto da lista, diminuiria muito uso de memória para buscas grandes
-pub struct GraphSearch<T> 
+/**
+ * Busca que guarda o caminho até cada vértice, e o custo acumulado dele (C)
+ * 
+ * - depth_first/breadth_first: vizinhos via GraphIterState, o custo é a quantidade de arestas do caminho
+ * - dijkstra: vizinhos via GraphCostState, sai sempre o caminho de menor custo acumulado.
+ *   Quando um vértice sai pela primeira vez no next(), o caminho até ele é o mais barato (custos não podem ser negativos)
+ * - a_star: igual ao dijkstra, mas ordenado por custo + heurística(vértice), uma estimativa do custo restante até o destino.
+ *   O caminho só é garantidamente o mais barato se a heurística nunca superestimar o custo restante
+ * - greedy_best_first, depth_limited, iterative_deepening, bidirectional: iguais aos do GraphIter.
+ *   No bidirectional, o caminho retornado ao se encontrarem vai do início até o destino
+ * 
+ * Como o caminho é guardado (P):
+ * - LinkedStack<T> (padrão): cada vértice a explorar tem o caminho inteiro, next() retorna o caminho pronto.
+ *   Os caminhos compartilham o começo (clonar o LinkedStack só copia uma referência), mas cada vértice na fronteira
+ *   mantém vivo um nó com Rc, e os nós de caminhos já descartados só são liberados quando ninguém mais aponta para eles
+ * - ParentLink<T> (with_parent_map): cada vértice a explorar guarda só o anterior, e ao visitar anota em um HashMap
+ *   (que também serve de lista de visitados). next() não tem o caminho, que é montado no get_path a partir do destino.
+ *   O mapa nunca libera nada, então não é sempre mais leve: o pico de memória pode ser maior que o do LinkedStack,
+ *   pois no LinkedStack os nós dos ramos já descartados são liberados, enquanto o mapa guarda todo vértice visitado
+ * 
+ * Comparação dos dois em um labirinto grande: benches/graph.rs
+ */
+pub struct GraphSearch<T, C = usize, P = LinkedStack<T>> 
 {
-    visited: HashSet<T>,
-    // Utilizando LinkedStack para ser econômico na memória copiar os caminhos
-    to_explore: VecDeque<LinkedStack<T>>,
-    current_path: Option<LinkedStack<T>>,
-    depth_first: bool
+    core: SearchCore<T, P, C>
 }
 
 impl<T> GraphSearch<T> 
@@ -80,109 +605,459 @@ where
     T: Clone + Eq + Hash
 {
     pub fn depth_first(start: T) -> Self {
-        return Self::new(true, start);
+        Self { core: SearchCore::new(SearchOrder::DepthFirst, start, None) }
     }
 
     pub fn breadth_first(start: T) -> Self {
-        return Self::new(false, start);
+        Self { core: SearchCore::new(SearchOrder::BreadthFirst, start, None) }
     }
 
-    fn new(depth_first: bool, start: T) -> Self {
-        let mut first_path = LinkedStack::new();
-        first_path.push(start);
+    pub fn depth_limited(start: T, limit: usize) -> Self {
+        Self { core: SearchCore::with_deepening(start, limit, limit) }
+    }
 
-        Self {
-            visited: HashSet::new(),
-            to_explore: VecDeque::from([first_path]),
-            current_path: None,
-            depth_first: depth_first
-        }
+    pub fn iterative_deepening(start: T, max_depth: usize) -> Self {
+        Self { core: SearchCore::with_deepening(start, 0, max_depth) }
     }
 
-    pub fn next(&mut self) -> Option<&LinkedStack<T>> {
-        while let Some(path) = if self.depth_first { 
-            self.to_explore.pop_back() 
-        } else { 
-            self.to_explore.pop_front() 
-        } {
-            let node_index = path.peek().unwrap().clone();
-            // Insere na lista, e se já está visitado deve pular este nó
-            if !self.visited.insert(node_index.clone()) {
-                continue;
-            }
+    pub fn bidirectional(start: T, goal: T) -> Self {
+        Self { core: SearchCore::with_bidirectional(start, goal) }
+    }
+}
 
-            self.current_path = Some(path);
-            return self.current_path.as_ref();
-        }
-        return None;
+impl<T, C> GraphSearch<T, C> 
+where
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default
+{
+    /**
+     * Custo C::default() é considerado zero
+     */
+    pub fn dijkstra(start: T) -> Self {
+        Self { core: SearchCore::new(SearchOrder::Cheapest, start, None) }
+    }
+
+    /**
+     * heuristic: estimativa do custo de um vértice até o destino (ex: distância de manhattan em um grid)
+     */
+    pub fn a_star<H: Fn(&T) -> C + 'static>(start: T, heuristic: H) -> Self {
+        Self { core: SearchCore::new(SearchOrder::Cheapest, start, Some(Box::new(heuristic))) }
+    }
+
+    /**
+     * Como o a_star sem o custo acumulado: rápido, mas o caminho encontrado pode não ser o melhor
+     */
+    pub fn greedy_best_first<H: Fn(&T) -> C + 'static>(start: T, heuristic: H) -> Self {
+        Self { core: SearchCore::new(SearchOrder::Greedy, start, Some(Box::new(heuristic))) }
+    }
+
+    /**
+     * Troca para guardar um mapa de pais no lugar dos caminhos, deve ser chamado antes do primeiro next()
+     * GraphSearch::breadth_first(inicio).with_parent_map()
+     */
+    pub fn with_parent_map(self) -> GraphSearch<T, C, ParentLink<T>> {
+        GraphSearch { core: self.core.convert() }
     }
 
     /** Deve chamar somente após encerrar iteração */
     pub fn get_path(self) -> LinkedStack<T> {
-        return self.current_path.unwrap();
+        return self.core.current.unwrap().extra;
+    }
+
+    /** Deve chamar somente após encerrar iteração, o caminho (destino no topo) e o custo total dele */
+    pub fn get_path_cost(self) -> (LinkedStack<T>, C) {
+        let current = self.core.current.unwrap();
+        return (current.extra, current.cost);
     }
 }
 
-impl<T> GraphIterState<T> for GraphSearch<T> 
+impl<T, C, P> GraphSearch<T, C, P> 
 where
-    T: Clone + Eq + Hash
-{  
-    fn push_neighbor(&mut self, neighbor: T) {
-        if !self.visited.contains(&neighbor) {
-            let mut path = self.current_path.as_ref().unwrap().clone();
-            path.push(neighbor);
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    P: PathRecord<T>
+{
+    pub fn next(&mut self) -> Option<&P> {
+        return self.core.next().map(|entry| &entry.extra);
+    }
+
+    /**
+     * Custo acumulado do caminho retornado pelo último next()
+     */
+    pub fn cost(&self) -> C {
+        return self.core.current.as_ref().unwrap().cost;
+    }
+
+    /**
+     * Bidirecional: vértice onde as buscas do início e do destino se encontraram
+     */
+    pub fn meeting_point(&self) -> Option<&T> {
+        return self.core.bidirectional.as_ref()?.meeting_point.as_ref();
+    }
+}
 
-            self.to_explore.push_back(path);
+impl<T, C> GraphSearch<T, C, ParentLink<T>> 
+where
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default
+{
+    /**
+     * Vértices do node até o início seguindo os pais: [node, pai, pai do pai, ..., início]
+     */
+    fn walk<'a, F: Fn(&T) -> Option<&'a T>>(node: &T, parent_of: F) -> Vec<T> where T: 'a {
+        let mut ret = vec![node.clone()];
+        while let Some(parent) = parent_of(ret.last().unwrap()) {
+            ret.push(parent.clone());
+        }
+        return ret;
+    }
+
+    /** Deve chamar somente após encerrar iteração, monta o caminho (destino no topo) voltando pelos pais */
+    pub fn get_path(self) -> LinkedStack<T> {
+        let current = self.core.current.unwrap();
+        let mut nodes = match &self.core.bidirectional {
+            Some(bidirectional) => {
+                // Cada lado tem os pais no seu mapa de visitados
+                let forward = |node: &T| Self::walk(node, |n| bidirectional.forward.get(n).and_then(|(link, _, _)| link.parent()));
+                let backward = |node: &T| Self::walk(node, |n| bidirectional.backward.get(n).and_then(|(link, _, _)| link.parent()));
+                match &bidirectional.meeting_point {
+                    // [destino ... encontro ... início]
+                    Some(meeting_point) => {
+                        let mut nodes = backward(meeting_point);
+                        nodes.reverse();
+                        nodes.pop();
+                        nodes.extend(forward(meeting_point));
+                        nodes
+                    },
+                    None if current.backward => backward(&current.node),
+                    None => forward(&current.node),
+                }
+            },
+            None => Self::walk(&current.node, |node| self.core.parents.get(node).and_then(|parent| parent.as_ref())),
+        };
+
+        let mut path = LinkedStack::new();
+        while let Some(node) = nodes.pop() {
+            path.push(node);
         }
+        return path;
+    }
+
+    /** Deve chamar somente após encerrar iteração, o caminho (destino no topo) e o custo total dele */
+    pub fn get_path_cost(self) -> (LinkedStack<T>, C) {
+        let cost = self.cost();
+        return (self.get_path(), cost);
+    }
+}
+
+impl<T, C, P> GraphCostState<T, C> for GraphSearch<T, C, P> 
+where
+    T: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    P: PathRecord<T>
+{  
+    fn push_neighbor(&mut self, neighbor: T, cost: C) {
+        self.core.push_neighbor(neighbor, cost);
+    }
+}
+
+/**
+ * Sem custo: conta 1 por aresta
+ */
+impl<T, P> GraphIterState<T> for GraphSearch<T, usize, P> 
+where
+    T: Clone + Eq + Hash,
+    P: PathRecord<T>
+{  
+    fn push_neighbor(&mut self, neighbor: T) {
+        self.core.push_neighbor(neighbor, 1);
     }
 }
 
 
-// Falta decidir como usar melhor isso
-pub struct GraphPool<T> {
-    vertices: VecPool<GraphPoolNode<T>>
+/**
+ * Grafo com os vértices em um VecPool, cada vértice com a lista das suas arestas (lista de adjacência)
+ * 
+ * - V: valor de cada vértice, E: peso/dados de cada aresta (padrão () para grafos sem peso)
+ * - Vértices são identificados pelo índice no pool retornado por add_vertex (continua válido ao remover outros vértices)
+ * - Direcionado: add_edge(a, b) cria somente a -> b
+ * - Não direcionado: add_edge(a, b) cria a -> b e b -> a (o peso é clonado), remove_edge remove as duas
+ * 
+ * Cada vértice também guarda de quais vértices chegam arestas (incoming), assim remove_vertex
+ * consegue limpar as arestas que apontam para ele sem percorrer o grafo inteiro
+ */
+pub struct GraphPool<V, E = ()> {
+    vertices: VecPool<GraphPoolNode<V, E>>,
+    directed: bool
 }
 
-struct GraphPoolNode<T> {
-    value: T,
-    conns: Vec<usize>
+struct GraphPoolNode<V, E> {
+    value: V,
+    edges: Vec<(usize, E)>,
+    incoming: Vec<usize>
 }
 
-impl<T> GraphPool<T> {
-    fn new() -> GraphPool<T> {
-        GraphPool { vertices: VecPool::new() }
+impl<V, E> GraphPool<V, E> {
+    /**
+     * Mesmo que directed()
+     */
+    pub fn new() -> GraphPool<V, E> {
+        Self::directed()
+    }
+
+    pub fn directed() -> GraphPool<V, E> {
+        GraphPool { vertices: VecPool::new(), directed: true }
+    }
+
+    pub fn undirected() -> GraphPool<V, E> {
+        GraphPool { vertices: VecPool::new(), directed: false }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
     }
 
-    fn get(&self, node_index: usize) -> &T {
+    pub fn get(&self, node_index: usize) -> &V {
         return &self.vertices[node_index].value;
     }
 
-    fn add_vertex(&mut self, value: T) -> usize {
-        self.vertices.alloc_node(GraphPoolNode { value: value, conns: Vec::new() })
+    pub fn get_mut(&mut self, node_index: usize) -> &mut V {
+        return &mut self.vertices[node_index].value;
+    }
+
+    pub fn contains(&self, node_index: usize) -> bool {
+        self.vertices.contains(node_index)
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /**
+     * Todos os índices de vértices são menores que isso (para guardar informações por vértice em um Vec)
+     */
+    pub fn index_bound(&self) -> usize {
+        self.vertices.arr_len()
+    }
+
+    /**
+     * Quantidade de arestas (no não direcionado cada aresta conta uma vez), O(V)
+     */
+    pub fn edge_count(&self) -> usize {
+        let mut total = 0;
+        let mut self_loops = 0;
+        for (index, node) in self.vertices.iter() {
+            total += node.edges.len();
+            self_loops += node.edges.iter().filter(|(to, _)| *to == index.index()).count();
+        }
+        if self.directed { return total; }
+        return (total + self_loops) / 2;
+    }
+
+    /**
+     * Atravessa os vértices (índice, valor), na ordem do pool
+     */
+    pub fn vertices(&self) -> impl Iterator<Item = (usize, &V)> + '_ {
+        self.vertices.iter().map(|(handle, node)| (handle.index(), &node.value))
+    }
+
+    pub fn add_vertex(&mut self, value: V) -> usize {
+        self.vertices.alloc_node(GraphPoolNode { value: value, edges: Vec::new(), incoming: Vec::new() }).index()
+    }
+
+    /**
+     * Remove o vértice, todas as arestas que saem dele e todas que chegam nele
+     */
+    pub fn remove_vertex(&mut self, node_index: usize) -> Option<V> {
+        let node = self.vertices.free_node(node_index)?;
+        for (to, _) in node.edges.iter() {
+            if *to != node_index {
+                let incoming = &mut self.vertices[*to].incoming;
+                if let Some(pos) = incoming.iter().position(|from| *from == node_index) {
+                    incoming.remove(pos);
+                }
+            }
+        }
+        for from in node.incoming.iter() {
+            if *from != node_index {
+                self.vertices[*from].edges.retain(|(to, _)| *to != node_index);
+            }
+        }
+        return Some(node.value);
+    }
+
+    /**
+     * Remove a aresta a -> b (e b -> a se não direcionado), retorna o peso dela
+     */
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> Option<E> {
+        let ret = self.unlink(a, b)?;
+        if !self.directed && a != b {
+            self.unlink(b, a);
+        }
+        return Some(ret);
+    }
+
+    fn unlink(&mut self, a: usize, b: usize) -> Option<E> {
+        let edges = &mut self.vertices.get_mut_node(a)?.edges;
+        let pos = edges.iter().position(|(to, _)| *to == b)?;
+        let (_, weight) = edges.remove(pos);
+
+        let incoming = &mut self.vertices[b].incoming;
+        if let Some(pos) = incoming.iter().position(|from| *from == a) {
+            incoming.remove(pos);
+        }
+        return Some(weight);
+    }
+
+    /**
+     * Peso da aresta a -> b, se existir
+     */
+    pub fn edge(&self, a: usize, b: usize) -> Option<&E> {
+        let node = self.vertices.get_node(a)?;
+        return node.edges.iter().find(|(to, _)| *to == b).map(|(_, weight)| weight);
+    }
+
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.edge(a, b).is_some()
+    }
+
+    /**
+     * Vértices para onde saem arestas de node_index, com o peso de cada aresta (na ordem de inserção)
+     */
+    pub fn neighbors(&self, node_index: usize) -> impl Iterator<Item = (usize, &E)> + '_ {
+        self.vertices[node_index].edges.iter().map(|(to, weight)| (*to, weight))
+    }
+
+    /**
+     * Vértices de onde chegam arestas em node_index (no não direcionado são os mesmos de neighbors)
+     */
+    pub fn incoming(&self, node_index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.vertices[node_index].incoming.iter().copied()
     }
 
-    fn connect(&mut self, a: usize, b: usize) {
-        self.vertices[a].conns.push(b);
+    /**
+     * Quantidade de arestas saindo do vértice (no não direcionado é o grau do vértice)
+     */
+    pub fn degree(&self, node_index: usize) -> usize {
+        self.vertices[node_index].edges.len()
     }
 
-    fn connect_bi(&mut self, a: usize, b: usize) {
-        self.vertices[a].conns.push(b);
-        self.vertices[b].conns.push(a);
+    /**
+     * Quantidade de arestas chegando no vértice (no não direcionado é igual a degree)
+     */
+    pub fn in_degree(&self, node_index: usize) -> usize {
+        self.vertices[node_index].incoming.len()
     }
 
-    fn visit<S: GraphIterState<usize>>(&self, node_index: usize, iter_state: &mut S) -> &T {
+    /**
+     * Compacta o pool de vértices, reescrevendo as arestas. Retorna o mapeamento de índices antigo -> novo
+     */
+    pub fn compact(&mut self) -> Vec<usize> {
+        let remap = self.vertices.compact();
+        for vertex in self.vertices.values_mut() {
+            for (to, _) in vertex.edges.iter_mut() {
+                *to = remap[*to];
+            }
+            for from in vertex.incoming.iter_mut() {
+                *from = remap[*from];
+            }
+        }
+        return remap;
+    }
+
+    /**
+     * Passa os vizinhos do vértice para o GraphIter/GraphSearch, e retorna o valor do vértice
+     */
+    pub fn visit<S: GraphIterState<usize>>(&self, node_index: usize, iter_state: &mut S) -> &V {
         let node = &self.vertices[node_index];
-        for neighbor in node.conns.iter() {
+        for (neighbor, _) in node.edges.iter() {
             iter_state.push_neighbor(*neighbor);
         }
         return &node.value;
     }
+
+    /**
+     * Igual ao visit, mas passa o peso de cada aresta como custo (GraphSearch::dijkstra, GraphSearch::a_star)
+     */
+    pub fn visit_cost<C, S: GraphCostState<usize, C>>(&self, node_index: usize, iter_state: &mut S) -> &V
+    where
+        E: Clone + Into<C>
+    {
+        let node = &self.vertices[node_index];
+        for (neighbor, weight) in node.edges.iter() {
+            iter_state.push_neighbor(*neighbor, weight.clone().into());
+        }
+        return &node.value;
+    }
+}
+
+impl<V, E: Clone> GraphPool<V, E> {
+    /**
+     * Cria a aresta a -> b com o peso informado (e b -> a se não direcionado)
+     * Se a aresta já existia, substitui o peso e retorna o antigo
+     * Panic se a ou b não existe (verifica os dois antes de mexer no grafo)
+     */
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: E) -> Option<E> {
+        for node_index in [a, b] {
+            assert!(self.vertices.contains(node_index), "GraphPool: vértice {} não existe", node_index);
+        }
+        if !self.directed && a != b {
+            self.link(b, a, weight.clone());
+        }
+        return self.link(a, b, weight);
+    }
+
+    // a e b já foram verificados no add_edge
+    fn link(&mut self, a: usize, b: usize, weight: E) -> Option<E> {
+        let edges = &mut self.vertices[a].edges;
+        if let Some((_, old)) = edges.iter_mut().find(|(to, _)| *to == b) {
+            return Some(std::mem::replace(old, weight));
+        }
+        edges.push((b, weight));
+        self.vertices[b].incoming.push(a);
+        return None;
+    }
+}
+
+/**
+ * Grafo não direcionado usado nos testes do graph, graph_algorithms e graph_format
+ * Retorna também os índices dos vértices [A, B, C, D, E, F]
+ *
+ *   E --- A
+ *   |     |
+ *   F     B
+ *    \  /   \
+ *     C ---- D
+ */
+#[cfg(test)]
+pub(crate) fn graph_fixture() -> (GraphPool<char, u32>, [usize; 6]) {
+    let mut graph = GraphPool::undirected();
+    let a = graph.add_vertex('A');
+    let b = graph.add_vertex('B');
+    let c = graph.add_vertex('C');
+    let d = graph.add_vertex('D');
+    let e = graph.add_vertex('E');
+    let f = graph.add_vertex('F');
+
+    graph.add_edge(a, b, 1);
+    graph.add_edge(a, e, 2);
+
+    graph.add_edge(b, d, 3);
+    graph.add_edge(b, c, 4);
+
+    graph.add_edge(c, d, 5);
+    graph.add_edge(c, f, 6);
+
+    graph.add_edge(f, e, 7);
+    return (graph, [a, b, c, d, e, f]);
 }
 
 #[cfg(test)]
 mod test {
     use std::ops::Index;
 
+    use crate::estruturas::Vec2D;
+
     use super::*;
 
     #[test]
@@ -238,32 +1113,8 @@ mod test {
 
     #[test]
     pub fn graph_struct() {
-        let mut graph: GraphPool<char> = GraphPool::new();
-
-        /**
-         *   E --- A
-         *   |     |
-         *   F     B*
-         *    \  /   \
-         *     C ---- D    
-         */
-        let a = graph.add_vertex('A');
-        let b = graph.add_vertex('B');
-        let c = graph.add_vertex('C');
-        let d = graph.add_vertex('D');
-        let e = graph.add_vertex('E');
-        let f = graph.add_vertex('F');
-
-        graph.connect_bi(a, b);
-        graph.connect_bi(a, e);
-
-        graph.connect_bi(b, d);
-        graph.connect_bi(b, c);
-
-        graph.connect_bi(c, d);
-        graph.connect_bi(c, f);
-
-        graph.connect_bi(f, e);
+        // B é o início das buscas
+        let (mut graph, [a, b, c, d, _, f]) = graph_fixture();
 
         // Traversal only
         {
@@ -298,5 +1149,486 @@ mod test {
             }
             assert_eq!(visited, String::from("FCB")); // porque é pilha é ao contrário
         }
+
+        // Arestas e graus
+        {
+            assert_eq!(graph.vertex_count(), 6);
+            assert_eq!(graph.edge_count(), 7);
+            assert_eq!(graph.edge(b, c), Some(&4));
+            assert_eq!(graph.edge(c, b), Some(&4));
+            assert_eq!(graph.edge(a, d), None);
+            assert_eq!(graph.neighbors(c).map(|(v, w)| (*graph.get(v), *w)).collect::<Vec<_>>(), vec![('B', 4), ('D', 5), ('F', 6)]);
+            assert_eq!(graph.degree(b), 3);
+            assert_eq!(graph.in_degree(b), 3);
+
+            // Substitui o peso
+            assert_eq!(graph.add_edge(d, c, 50), Some(5));
+            assert_eq!(graph.edge(c, d), Some(&50));
+            assert_eq!(graph.edge_count(), 7);
+
+            assert_eq!(graph.remove_edge(c, d), Some(50));
+            assert_eq!(graph.remove_edge(d, c), None);
+            assert!(!graph.has_edge(d, c));
+            assert_eq!(graph.degree(d), 1);
+            assert_eq!(graph.edge_count(), 6);
+        }
+
+        // Remover vértice limpa as arestas que chegam nele
+        {
+            assert_eq!(graph.remove_vertex(b), Some('B'));
+            assert_eq!(graph.remove_vertex(b), None);
+            assert!(!graph.contains(b));
+            assert_eq!(graph.vertex_count(), 5);
+            assert_eq!(graph.edge_count(), 3);
+            assert_eq!(graph.degree(a), 1);
+            assert_eq!(graph.degree(d), 0);
+            assert_eq!(graph.neighbors(c).map(|(v, _)| *graph.get(v)).collect::<String>(), "F");
+
+            let mut visited = String::new();
+            let mut iter_state = GraphIter::breadth_first(a);
+            while let Some(node_index) = iter_state.next() {
+                visited.push(*graph.visit(node_index, &mut iter_state));
+            }
+            assert_eq!(visited, String::from("AEFC"));
+
+            // Os índices mudam ao compactar, mas as arestas continuam as mesmas
+            let remap = graph.compact();
+            let (a, c, f) = (remap[a], remap[c], remap[f]);
+            assert_eq!(graph.get(c), &'C');
+            assert_eq!(graph.edge(f, c), Some(&6));
+            assert_eq!(graph.in_degree(a), 1);
+            assert_eq!(graph.vertices().map(|(_, v)| *v).collect::<String>(), "ACDEF");
+        }
+    }
+
+    #[test]
+    pub fn graph_directed() {
+        let mut graph: GraphPool<&str, f32> = GraphPool::directed();
+        assert!(graph.is_directed());
+        let casa = graph.add_vertex("casa");
+        let escola = graph.add_vertex("escola");
+        let mercado = graph.add_vertex("mercado");
+
+        graph.add_edge(casa, escola, 1.5);
+        graph.add_edge(escola, mercado, 0.5);
+        graph.add_edge(mercado, casa, 2.0);
+        graph.add_edge(casa, casa, 0.0);
+
+        assert!(graph.has_edge(casa, escola));
+        assert!(!graph.has_edge(escola, casa));
+        assert_eq!(graph.degree(casa), 2);
+        assert_eq!(graph.in_degree(casa), 2);
+        assert_eq!(graph.edge_count(), 4);
+
+        *graph.get_mut(mercado) = "feira";
+        assert_eq!(graph.remove_vertex(casa), Some("casa"));
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.degree(mercado), 0);
+        assert_eq!(graph.in_degree(escola), 0);
+        assert_eq!(graph.neighbors(escola).map(|(v, w)| (*graph.get(v), *w)).collect::<Vec<_>>(), vec![("feira", 0.5)]);
+
+        // Laço no não direcionado conta uma vez
+        let mut graph: GraphPool<char> = GraphPool::undirected();
+        let x = graph.add_vertex('X');
+        let y = graph.add_vertex('Y');
+        graph.add_edge(x, x, ());
+        graph.add_edge(x, y, ());
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.degree(x), 2);
+        assert_eq!(graph.remove_edge(x, x), Some(()));
+        assert_eq!(graph.degree(x), 1);
+        assert_eq!(graph.in_degree(x), 1);
+    }
+
+    #[test]
+    pub fn graph_add_edge_missing_vertex() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        for mut graph in [GraphPool::<char>::directed(), GraphPool::<char>::undirected()] {
+            let x = graph.add_vertex('X');
+            let y = graph.add_vertex('Y');
+            graph.remove_vertex(y);
+            // Qualquer uma das pontas faltando é panic, sem deixar meia aresta para trás
+            for (a, b) in [(x, y), (y, x), (x, 5), (5, x)] {
+                let err = catch_unwind(AssertUnwindSafe(|| graph.add_edge(a, b, ()))).unwrap_err();
+                let missing = if a == x { b } else { a };
+                assert_eq!(err.downcast_ref::<String>().unwrap(), &format!("GraphPool: vértice {} não existe", missing));
+                assert_eq!(graph.edge_count(), 0);
+                assert_eq!(graph.degree(x), 0);
+                assert_eq!(graph.in_degree(x), 0);
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn graph_dijkstra() {
+        // Mesmo formato do graph_struct, com outros pesos
+        let mut graph: GraphPool<char, u32> = GraphPool::undirected();
+        let a = graph.add_vertex('A');
+        let b = graph.add_vertex('B');
+        let c = graph.add_vertex('C');
+        let d = graph.add_vertex('D');
+        let e = graph.add_vertex('E');
+        let f = graph.add_vertex('F');
+
+        graph.add_edge(a, b, 1);
+        graph.add_edge(a, e, 1);
+        graph.add_edge(b, d, 1);
+        graph.add_edge(b, c, 5);
+        graph.add_edge(c, d, 1);
+        graph.add_edge(c, f, 2);
+        graph.add_edge(f, e, 4);
+
+        let mut visited = String::new();
+        let mut costs = Vec::new();
+        let mut iter_state = GraphSearch::<usize, u32>::dijkstra(b);
+        while let Some(path) = iter_state.next() {
+            let node_index = *path.peek().unwrap();
+            costs.push(iter_state.cost());
+            visited.push(*graph.visit_cost(node_index, &mut iter_state));
+            if node_index == f {
+                break;
+            }
+        }
+        assert_eq!(visited, String::from("BADECF"));
+        assert_eq!(costs, vec![0, 1, 1, 2, 2, 4]);
+
+        let (path, cost) = iter_state.get_path_cost();
+        assert_eq!(path.iter().map(|v| *graph.get(*v)).collect::<String>(), "FCDB");
+        assert_eq!(cost, 4);
+
+        // Sem pesos o breadth_first conta as arestas
+        let mut iter_state = GraphSearch::breadth_first(b);
+        while let Some(path) = iter_state.next() {
+            let node_index = *path.peek().unwrap();
+            graph.visit(node_index, &mut iter_state);
+            if node_index == f { break; }
+        }
+        assert_eq!(iter_state.cost(), 2);
+        assert_eq!(iter_state.get_path().iter().map(|v| *graph.get(*v)).collect::<String>(), "FCB");
+    }
+
+    #[test]
+    pub fn graph_a_star() {
+        // Grid implícito, como no dungeon: # parede, ~ lama (custo 5), o resto custo 1
+        let map = [
+            "S.~..",
+            ".#~#.",
+            ".#.#.",
+            "....G",
+        ];
+        let mut grid = Vec2D::new(5, 4, '.');
+        for (y, line) in map.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[(x, y)] = c;
+            }
+        }
+
+        fn visit_grid<S: GraphCostState<(i32, i32), u32>>(grid: &Vec2D<char>, (x, y): (i32, i32), iter_state: &mut S) {
+            for neigh in [(x+1, y), (x, y-1), (x-1, y), (x, y+1)] {
+                match grid.get(neigh) {
+                    Some('#') | None => {},
+                    Some('~') => iter_state.push_neighbor(neigh, 5),
+                    Some(_) => iter_state.push_neighbor(neigh, 1),
+                }
+            }
+        }
+
+        fn run(grid: &Vec2D<char>, mut iter_state: GraphSearch<(i32, i32), u32>) -> (Vec<(i32, i32)>, u32, usize) {
+            let mut count = 0;
+            while let Some(path) = iter_state.next() {
+                let pos = *path.peek().unwrap();
+                count += 1;
+                if grid.get(pos) == Some(&'G') {
+                    let (path, cost) = iter_state.get_path_cost();
+                    let mut path: Vec<(i32, i32)> = path.iter().copied().collect();
+                    path.reverse();
+                    return (path, cost, count);
+                }
+                visit_grid(grid, pos, &mut iter_state);
+            }
+            panic!("Não encontrou o destino");
+        }
+
+        let goal = (4, 3);
+        let (path_dijkstra, cost_dijkstra, count_dijkstra) = run(&grid, GraphSearch::dijkstra((0, 0)));
+        let (path_a_star, cost_a_star, count_a_star) = run(&grid, GraphSearch::a_star((0, 0), move |&(x, y): &(i32, i32)| {
+            ((goal.0 - x).abs() + (goal.1 - y).abs()) as u32
+        }));
+
+        let expected = vec![(0,0), (0,1), (0,2), (0,3), (1,3), (2,3), (3,3), (4,3)];
+        assert_eq!(path_dijkstra, expected);
+        assert_eq!(path_a_star, expected);
+        assert_eq!(cost_dijkstra, 7);
+        assert_eq!(cost_a_star, 7);
+        // A heurística evita explorar o que está longe do destino
+        assert!(count_a_star < count_dijkstra, "{} < {}", count_a_star, count_dijkstra);
+    }
+
+    fn maze() -> Vec2D<char> {
+        let map = [
+            "S...#..",
+            ".##.#.#",
+            ".#..#..",
+            ".#.##.#",
+            "......G",
+        ];
+        let mut grid = Vec2D::new(7, 5, '.');
+        for (y, line) in map.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[(x, y)] = c;
+            }
+        }
+        return grid;
+    }
+
+    // O mesmo visitante para todas as estratégias
+    fn visit_maze<S: GraphIterState<(i32, i32)>>(grid: &Vec2D<char>, (x, y): (i32, i32), iter_state: &mut S) {
+        for neigh in [(x+1, y), (x, y-1), (x-1, y), (x, y+1)] {
+            if let Some(c) = grid.get(neigh) && *c != '#' {
+                iter_state.push_neighbor(neigh);
+            }
+        }
+    }
+
+    #[test]
+    pub fn graph_strategies() {
+        let grid = maze();
+        let (start, goal) = ((0, 0), (6, 4));
+
+        // Retorna o caminho do início ao destino e quantos vértices foram retornados pelo next()
+        fn run(grid: &Vec2D<char>, goal: (i32, i32), mut iter_state: GraphSearch<(i32, i32)>) -> Option<(Vec<(i32, i32)>, usize)> {
+            let mut count = 0;
+            while let Some(path) = iter_state.next() {
+                let pos = *path.peek().unwrap();
+                count += 1;
+                if pos == goal {
+                    let mut path: Vec<(i32, i32)> = iter_state.get_path().iter().copied().collect();
+                    path.reverse();
+                    return Some((path, count));
+                }
+                visit_maze(grid, pos, &mut iter_state);
+            }
+            return None;
+        }
+
+        fn assert_valid(grid: &Vec2D<char>, path: &[(i32, i32)]) {
+            for pair in path.windows(2) {
+                assert_eq!((pair[0].0 - pair[1].0).abs() + (pair[0].1 - pair[1].1).abs(), 1);
+                assert_ne!(grid.get(pair[1]), Some(&'#'));
+            }
+        }
+
+        let manhattan = move |&(x, y): &(i32, i32)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as usize;
+
+        let (bfs_path, bfs_count) = run(&grid, goal, GraphSearch::breadth_first(start)).unwrap();
+        assert_eq!(bfs_path.len(), 11);
+
+        let (greedy_path, greedy_count) = run(&grid, goal, GraphSearch::greedy_best_first(start, manhattan)).unwrap();
+        assert_valid(&grid, &greedy_path);
+        assert_eq!(greedy_path.first(), Some(&start));
+        assert!(greedy_count < bfs_count, "{} < {}", greedy_count, bfs_count);
+
+        // Com limite menor que a distância não encontra
+        assert_eq!(run(&grid, goal, GraphSearch::depth_limited(start, 9)), None);
+        let (limited_path, _) = run(&grid, goal, GraphSearch::depth_limited(start, 10)).unwrap();
+        assert_eq!(limited_path, bfs_path);
+
+        // Aprofundamento iterativo encontra o caminho mais curto, repetindo vértices a cada rodada
+        let (iddfs_path, iddfs_count) = run(&grid, goal, GraphSearch::iterative_deepening(start, 100)).unwrap();
+        assert_eq!(iddfs_path, bfs_path);
+        assert!(iddfs_count > bfs_count);
+        assert_eq!(run(&grid, goal, GraphSearch::iterative_deepening(start, 5)), None);
+
+        // Bidirecional: o destino também sai no next() (início da busca de trás para frente),
+        // então segue até o fim, o último caminho retornado é o completo
+        let mut search = GraphSearch::bidirectional(start, goal);
+        while let Some(path) = search.next() {
+            let pos = *path.peek().unwrap();
+            visit_maze(&grid, pos, &mut search);
+        }
+        let meeting_point = *search.meeting_point().unwrap();
+        assert_eq!(search.cost(), 10);
+        let mut bi_path: Vec<(i32, i32)> = search.get_path().iter().copied().collect();
+        bi_path.reverse();
+        assert_valid(&grid, &bi_path);
+        assert_eq!(bi_path.len(), 11);
+        assert_eq!(bi_path.first(), Some(&start));
+        assert_eq!(bi_path.last(), Some(&goal));
+        assert!(bi_path.contains(&meeting_point));
+
+        // GraphIter com as mesmas estratégias
+        let mut iter = GraphIter::iterative_deepening(start, 100);
+        let mut depth = None;
+        while let Some(pos) = iter.next() {
+            if pos == goal {
+                depth = Some(iter.depth());
+                break;
+            }
+            visit_maze(&grid, pos, &mut iter);
+        }
+        assert_eq!(depth, Some(10));
+
+        let mut iter = GraphIter::bidirectional(start, goal);
+        let mut last = None;
+        while let Some(pos) = iter.next() {
+            last = Some(pos);
+            visit_maze(&grid, pos, &mut iter);
+        }
+        assert_eq!(last, Some(goal));
+        assert_eq!(iter.depth(), 10);
+        assert!(iter.meeting_point().is_some());
+
+        let mut iter = GraphIter::greedy_best_first(start, manhattan);
+        let mut count = 0;
+        while let Some(pos) = iter.next() {
+            count += 1;
+            if pos == goal { break; }
+            visit_maze(&grid, pos, &mut iter);
+        }
+        assert_eq!(count, greedy_count);
+    }
+
+    #[test]
+    pub fn graph_parent_map() {
+        type Pos = (i32, i32);
+        let grid = maze();
+        let (start, goal) = ((0, 0), (6, 4));
+
+        // Mesmo código de visita, peek() do ParentLink retorna o vértice
+        fn run(grid: &Vec2D<char>, goal: Pos, mut iter_state: GraphSearch<Pos, usize, ParentLink<Pos>>) -> Option<(Vec<Pos>, usize)> {
+            while let Some(link) = iter_state.next() {
+                let pos = *link.peek().unwrap();
+                if pos == goal {
+                    let (path, cost) = iter_state.get_path_cost();
+                    let mut path: Vec<Pos> = path.iter().copied().collect();
+                    path.reverse();
+                    return Some((path, cost));
+                }
+                visit_maze(grid, pos, &mut iter_state);
+            }
+            return None;
+        }
+
+        fn run_stack(grid: &Vec2D<char>, goal: Pos, mut iter_state: GraphSearch<Pos>) -> Option<(Vec<Pos>, usize)> {
+            while let Some(path) = iter_state.next() {
+                let pos = *path.peek().unwrap();
+                if pos == goal {
+                    let (path, cost) = iter_state.get_path_cost();
+                    let mut path: Vec<Pos> = path.iter().copied().collect();
+                    path.reverse();
+                    return Some((path, cost));
+                }
+                visit_maze(grid, pos, &mut iter_state);
+            }
+            return None;
+        }
+
+        // Os dois modos encontram o mesmo caminho
+        for strategy in 0..4 {
+            let new = || match strategy {
+                0 => GraphSearch::breadth_first(start),
+                1 => GraphSearch::depth_first(start),
+                2 => GraphSearch::iterative_deepening(start, 100),
+                _ => GraphSearch::depth_limited(start, 9),
+            };
+            let with_stack = run_stack(&grid, goal, new());
+            let with_parents = run(&grid, goal, new().with_parent_map());
+            assert_eq!(with_stack, with_parents, "estratégia {}", strategy);
+            if strategy != 3 {
+                let (path, cost) = with_parents.unwrap();
+                assert_eq!(path.first(), Some(&start));
+                assert_eq!(path.len(), cost + 1);
+            }
+        }
+
+        // Pais de cada vértice visitado
+        let mut search = GraphSearch::breadth_first(start).with_parent_map();
+        let first = search.next().unwrap().clone();
+        assert_eq!(first.parent(), None);
+        visit_maze(&grid, *first.node(), &mut search);
+        let second = search.next().unwrap();
+        assert_eq!((second.node(), second.parent()), (&(1, 0), Some(&start)));
+
+        // Bidirecional: junta os pais dos dois lados
+        let mut search = GraphSearch::bidirectional(start, goal).with_parent_map();
+        while let Some(link) = search.next() {
+            let pos = *link.peek().unwrap();
+            visit_maze(&grid, pos, &mut search);
+        }
+        assert!(search.meeting_point().is_some());
+        let (path, cost) = search.get_path_cost();
+        let path: Vec<Pos> = path.iter().copied().collect();
+        assert_eq!(cost, 10);
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&goal));
+        assert_eq!(path.last(), Some(&start));
+        for pair in path.windows(2) {
+            assert_eq!((pair[0].0 - pair[1].0).abs() + (pair[0].1 - pair[1].1).abs(), 1);
+        }
+
+        // Com custo, no grafo do graph_dijkstra
+        let mut graph: GraphPool<char, u32> = GraphPool::undirected();
+        let v: Vec<usize> = "ABCDEF".chars().map(|c| graph.add_vertex(c)).collect();
+        for (a, b, w) in [(0, 1, 1), (0, 4, 1), (1, 3, 1), (1, 2, 5), (2, 3, 1), (2, 5, 2), (5, 4, 4)] {
+            graph.add_edge(v[a], v[b], w);
+        }
+        let mut search = GraphSearch::<usize, u32>::dijkstra(v[1]).with_parent_map();
+        while let Some(link) = search.next() {
+            let node_index = *link.node();
+            if node_index == v[5] { break; }
+            graph.visit_cost(node_index, &mut search);
+        }
+        let (path, cost) = search.get_path_cost();
+        assert_eq!(path.iter().map(|v| *graph.get(*v)).collect::<String>(), "FCDB");
+        assert_eq!(cost, 4);
+    }
+
+    #[test]
+    pub fn graph_visited_set() {
+        let grid = maze();
+        let (w, h) = grid.size();
+
+        fn run<V: VisitedSet<(i32, i32)>>(grid: &Vec2D<char>, iter_state: &mut GraphIter<(i32, i32), usize, V>) -> Vec<(i32, i32)> {
+            let mut visited = Vec::new();
+            while let Some(pos) = iter_state.next() {
+                visited.push(pos);
+                visit_maze(grid, pos, iter_state);
+            }
+            return visited;
+        }
+
+        // Mesma ordem com HashSet e com Vec2D<bool>
+        let expected = run(&grid, &mut GraphIter::breadth_first((0, 0)));
+        let mut iter_state = GraphIter::breadth_first((0, 0)).with_visited(Vec2D::new(w, h, false));
+        assert_eq!(run(&grid, &mut iter_state), expected);
+        assert_eq!(expected.len(), grid.values().filter(|c| **c != '#').count());
+
+        // Reaproveitando o mesmo GraphIter
+        iter_state.reset((0, 0));
+        assert_eq!(run(&grid, &mut iter_state), expected);
+        iter_state.reset((6, 4));
+        let from_goal = run(&grid, &mut iter_state);
+        assert_eq!(from_goal, run(&grid, &mut GraphIter::breadth_first((6, 4))));
+        assert_eq!(from_goal.len(), expected.len());
+
+        // Posições marcadas antes de começar não são visitadas
+        let mut blocked = Vec2D::new(w, h, false);
+        blocked[(0, 1)] = true;
+        let visited = run(&grid, &mut GraphIter::depth_first((0, 0)).with_visited(blocked));
+        assert!(!visited.contains(&(0, 1)));
+        assert!(visited.contains(&(6, 4)));
+
+        // Reset também volta o limite do iterative_deepening e os dois lados do bidirectional
+        let mut iter_state = GraphIter::iterative_deepening((0, 0), 100);
+        let first = run(&grid, &mut iter_state);
+        iter_state.reset((0, 0));
+        assert_eq!(run(&grid, &mut iter_state), first);
+
+        let mut iter_state = GraphIter::bidirectional((0, 0), (6, 4));
+        let first = run(&grid, &mut iter_state);
+        iter_state.reset((0, 0));
+        assert_eq!(run(&grid, &mut iter_state), first);
+        assert!(iter_state.meeting_point().is_some());
+    }
+}
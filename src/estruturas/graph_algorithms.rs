@@ -0,0 +1,443 @@
+use std::{collections::VecDeque, error::Error, fmt};
+
+use super::{GraphPool, PriorityQueue, UnionFind};
+
+/**
+ * Erro do topological_sort, com um ciclo encontrado no grafo
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphCycleError {
+    // Vértices do ciclo em ordem: cycle[0] -> cycle[1] -> ... -> cycle[0]
+    pub cycle: Vec<usize>
+}
+
+impl fmt::Display for GraphCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "o grafo tem um ciclo: {:?}", self.cycle)
+    }
+}
+
+impl Error for GraphCycleError {}
+
+/**
+ * Algoritmos clássicos sobre o GraphPool, usando somente a API pública dele
+ * Informações por vértice ficam em Vec de tamanho index_bound(), indexados pelo índice do vértice
+ */
+impl<V, E> GraphPool<V, E> {
+    /**
+     * Ordenação topológica (algoritmo de Kahn): cada vértice aparece antes de todos para onde ele tem aresta
+     *
+     * - Começa pelos vértices sem arestas chegando, ao 'remover' um vértice diminui o grau de entrada dos vizinhos
+     * - Se sobrar vértice, ele está em um ciclo (ou depois de um), e o erro retorna um ciclo
+     *
+     * Em grafo não direcionado qualquer aresta é um ciclo a -> b -> a
+     */
+    pub fn topological_sort(&self) -> Result<Vec<usize>, GraphCycleError> {
+        let mut in_degree = vec![0; self.index_bound()];
+        let mut to_explore = VecDeque::new();
+        for (v, _) in self.vertices() {
+            in_degree[v] = self.in_degree(v);
+            if in_degree[v] == 0 {
+                to_explore.push_back(v);
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.vertex_count());
+        while let Some(v) = to_explore.pop_front() {
+            order.push(v);
+            for (w, _) in self.neighbors(v) {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    to_explore.push_back(w);
+                }
+            }
+        }
+
+        if order.len() == self.vertex_count() {
+            return Ok(order);
+        }
+
+        // Todo vértice que sobrou tem uma aresta chegando de outro que sobrou,
+        // então andando para trás por eles em algum momento repete um vértice
+        let mut walk: Vec<usize> = Vec::new();
+        let mut position = vec![usize::MAX; self.index_bound()];
+        let mut v = self.vertices().map(|(v, _)| v).find(|v| in_degree[*v] > 0).unwrap();
+        while position[v] == usize::MAX {
+            position[v] = walk.len();
+            walk.push(v);
+            v = self.incoming(v).find(|from| in_degree[*from] > 0).unwrap();
+        }
+
+        let mut cycle = walk.split_off(position[v]);
+        cycle.reverse();
+        return Err(GraphCycleError { cycle: cycle });
+    }
+
+    /**
+     * Componentes fortemente conexos (Tarjan): em cada componente todo vértice alcança todos os outros
+     *
+     * - Busca em profundidade numerando os vértices na ordem de visita (index)
+     * - lowlink: menor index alcançável a partir do vértice ficando dentro da pilha da busca
+     * - Quando lowlink == index o vértice é a 'raiz' de um componente, que é desempilhado inteiro
+     *
+     * Iterativo (pilha de chamadas em um Vec) para não estourar a pilha em grafos grandes.
+     * Os componentes saem em ordem topológica reversa, e os vértices de cada um em ordem crescente
+     */
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let unvisited = usize::MAX;
+        let mut index = vec![unvisited; self.index_bound()];
+        let mut lowlink = vec![0; self.index_bound()];
+        let mut on_stack = vec![false; self.index_bound()];
+        let mut stack = Vec::new();
+        let mut counter = 0;
+        let mut components = Vec::new();
+
+        for (start, _) in self.vertices() {
+            if index[start] != unvisited { continue; }
+
+            index[start] = counter;
+            lowlink[start] = counter;
+            counter += 1;
+            stack.push(start);
+            on_stack[start] = true;
+            let mut calls = vec![(start, self.neighbors(start))];
+
+            while let Some((v, neighbors)) = calls.last_mut() {
+                let v = *v;
+                match neighbors.next() {
+                    Some((w, _)) if index[w] == unvisited => {
+                        index[w] = counter;
+                        lowlink[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, self.neighbors(w)));
+                    },
+                    Some((w, _)) => {
+                        if on_stack[w] {
+                            lowlink[v] = lowlink[v].min(index[w]);
+                        }
+                    },
+                    None => {
+                        calls.pop();
+                        if let Some((parent, _)) = calls.last() {
+                            lowlink[*parent] = lowlink[*parent].min(lowlink[v]);
+                        }
+
+                        if lowlink[v] == index[v] {
+                            let mut component = Vec::new();
+                            while let Some(w) = stack.pop() {
+                                on_stack[w] = false;
+                                component.push(w);
+                                if w == v { break; }
+                            }
+                            component.sort();
+                            components.push(component);
+                        }
+                    },
+                }
+            }
+        }
+        return components;
+    }
+
+    /**
+     * Componentes conexos com UnionFind, ignorando a direção das arestas (fracamente conexos no direcionado)
+     * Ordenados pelo menor vértice, e os vértices de cada um em ordem crescente
+     */
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut sets = UnionFind::new(self.index_bound());
+        for (v, _) in self.vertices() {
+            for (w, _) in self.neighbors(v) {
+                sets.union(v, w);
+            }
+        }
+
+        let mut component_of = vec![usize::MAX; self.index_bound()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for (v, _) in self.vertices() {
+            let root = sets.find(v);
+            if component_of[root] == usize::MAX {
+                component_of[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[component_of[root]].push(v);
+        }
+        return components;
+    }
+
+    /**
+     * Divide os vértices em dois lados de forma que toda aresta ligue um lado ao outro,
+     * colorindo com busca em largura (ignorando a direção das arestas). None se não for possível (ciclo ímpar)
+     */
+    pub fn bipartition(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let mut side: Vec<Option<bool>> = vec![None; self.index_bound()];
+        for (start, _) in self.vertices() {
+            if side[start].is_some() { continue; }
+
+            side[start] = Some(false);
+            let mut to_explore = VecDeque::from([start]);
+            while let Some(v) = to_explore.pop_front() {
+                let other = side[v].map(|s| !s);
+                for w in self.neighbors(v).map(|(w, _)| w).chain(self.incoming(v)) {
+                    match side[w] {
+                        None => {
+                            side[w] = other;
+                            to_explore.push_back(w);
+                        },
+                        Some(s) => {
+                            if Some(s) != other { return None; }
+                        },
+                    }
+                }
+            }
+        }
+
+        let mut ret = (Vec::new(), Vec::new());
+        for (v, _) in self.vertices() {
+            if side[v] == Some(false) { ret.0.push(v); } else { ret.1.push(v); }
+        }
+        return Some(ret);
+    }
+
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_some()
+    }
+}
+
+impl<V, E: Ord + Clone> GraphPool<V, E> {
+    /**
+     * Árvore geradora mínima (Kruskal): as arestas em ordem crescente de peso,
+     * adicionando cada uma que liga dois componentes diferentes (UnionFind)
+     *
+     * Retorna as arestas (a, b, peso). Se o grafo não for conexo, é uma floresta (uma árvore por componente)
+     * None se o grafo for direcionado (árvore geradora mínima somente em grafo não direcionado)
+     */
+    pub fn minimum_spanning_tree_kruskal(&self) -> Option<Vec<(usize, usize, E)>> {
+        if self.is_directed() { return None; }
+
+        let mut edges = Vec::new();
+        for (a, _) in self.vertices() {
+            for (b, weight) in self.neighbors(a) {
+                // Cada aresta aparece nos dois vértices
+                if a < b {
+                    edges.push((a, b, weight.clone()));
+                }
+            }
+        }
+        edges.sort_by(|x, y| x.2.cmp(&y.2));
+
+        let mut sets = UnionFind::new(self.index_bound());
+        let mut ret = Vec::new();
+        for (a, b, weight) in edges {
+            if sets.union(a, b) {
+                ret.push((a, b, weight));
+            }
+        }
+        return Some(ret);
+    }
+
+    /**
+     * Árvore geradora mínima (Prim): cresce a árvore a partir de um vértice,
+     * sempre pela aresta mais barata que sai dela (PriorityQueue)
+     *
+     * Mesmo resultado (peso total) do Kruskal, recomeça em cada componente não alcançado. None se o grafo for direcionado
+     */
+    pub fn minimum_spanning_tree_prim(&self) -> Option<Vec<(usize, usize, E)>> {
+        if self.is_directed() { return None; }
+
+        let mut in_tree = vec![false; self.index_bound()];
+        let mut ret = Vec::new();
+        for (start, _) in self.vertices() {
+            if in_tree[start] { continue; }
+
+            in_tree[start] = true;
+            let mut to_explore = PriorityQueue::new();
+            for (w, weight) in self.neighbors(start) {
+                to_explore.push((start, w), weight.clone());
+            }
+
+            while let Some(((a, b), weight)) = to_explore.pop() {
+                if in_tree[b] { continue; }
+
+                in_tree[b] = true;
+                ret.push((a, b, weight));
+                for (w, weight) in self.neighbors(b) {
+                    if !in_tree[w] {
+                        to_explore.push((b, w), weight.clone());
+                    }
+                }
+            }
+        }
+        return Some(ret);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::estruturas::{graph::graph_fixture as fixture, GraphPool};
+
+    fn names<E>(graph: &GraphPool<char, E>, vertices: &[usize]) -> String {
+        vertices.iter().map(|v| *graph.get(*v)).collect()
+    }
+
+    #[test]
+    pub fn graph_topological_sort() {
+        // Ordem para se vestir
+        let mut graph: GraphPool<&str> = GraphPool::directed();
+        let cueca = graph.add_vertex("cueca");
+        let meia = graph.add_vertex("meia");
+        let calca = graph.add_vertex("calça");
+        let sapato = graph.add_vertex("sapato");
+        let camisa = graph.add_vertex("camisa");
+        let gravata = graph.add_vertex("gravata");
+        let cinto = graph.add_vertex("cinto");
+        let paleto = graph.add_vertex("paletó");
+
+        for (a, b) in [(cueca, calca), (calca, sapato), (meia, sapato), (camisa, gravata), (gravata, paleto), (calca, cinto), (cinto, paleto)] {
+            graph.add_edge(a, b, ());
+        }
+
+        let order = graph.topological_sort().unwrap();
+        assert_eq!(order.iter().map(|v| *graph.get(*v)).collect::<Vec<_>>(),
+            vec!["cueca", "meia", "camisa", "calça", "gravata", "sapato", "cinto", "paletó"]);
+
+        // Ciclo: paletó -> cueca -> calça -> cinto -> paletó
+        graph.add_edge(paleto, cueca, ());
+        let err = graph.topological_sort().unwrap_err();
+        assert_eq!(err.cycle.len(), 4);
+        for i in 0..err.cycle.len() {
+            assert!(graph.has_edge(err.cycle[i], err.cycle[(i + 1) % err.cycle.len()]));
+        }
+        assert!(err.to_string().starts_with("o grafo tem um ciclo"));
+
+        // Laço
+        graph.remove_edge(paleto, cueca);
+        graph.add_edge(sapato, sapato, ());
+        assert_eq!(graph.topological_sort(), Err(super::GraphCycleError { cycle: vec![sapato] }));
+
+        let (graph, [a, b, ..]) = fixture();
+        let cycle = graph.topological_sort().unwrap_err().cycle;
+        assert_eq!(cycle.len(), 2);
+        assert!(cycle.contains(&a) || cycle.contains(&b));
+    }
+
+    #[test]
+    pub fn graph_strongly_connected() {
+        let mut graph: GraphPool<char> = GraphPool::directed();
+        let v: Vec<usize> = "ABCDEFGH".chars().map(|c| graph.add_vertex(c)).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (1, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)] {
+            graph.add_edge(v[a], v[b], ());
+        }
+
+        let components: Vec<String> = graph.strongly_connected_components().iter().map(|c| names(&graph, c)).collect();
+        assert_eq!(components, vec!["DEF", "ABC", "GH"]);
+
+        // Sem ciclos cada vértice é um componente
+        graph.remove_edge(v[2], v[0]);
+        graph.remove_edge(v[5], v[3]);
+        graph.remove_edge(v[7], v[6]);
+        assert_eq!(graph.strongly_connected_components().len(), 8);
+
+        // Não direcionado: componentes fortemente conexos são os componentes conexos
+        let (graph, _) = fixture();
+        assert_eq!(graph.strongly_connected_components().len(), 1);
+
+        // Caminho longo não estoura a pilha
+        let mut graph: GraphPool<usize> = GraphPool::directed();
+        let size = 100_000;
+        for i in 0..size {
+            graph.add_vertex(i);
+            if i > 0 { graph.add_edge(i - 1, i, ()); }
+        }
+        graph.add_edge(size - 1, 0, ());
+        assert_eq!(graph.strongly_connected_components().len(), 1);
+    }
+
+    #[test]
+    pub fn graph_connected_components() {
+        let (mut graph, [a, b, _, _, e, _]) = fixture();
+        let g = graph.add_vertex('G');
+        let h = graph.add_vertex('H');
+        graph.add_vertex('I');
+        graph.add_edge(g, h, 10);
+
+        let components: Vec<String> = graph.connected_components().iter().map(|c| names(&graph, c)).collect();
+        assert_eq!(components, vec!["ABCDEF", "GH", "I"]);
+
+        graph.remove_edge(a, b);
+        graph.remove_edge(a, e);
+        let components: Vec<String> = graph.connected_components().iter().map(|c| names(&graph, c)).collect();
+        assert_eq!(components, vec!["A", "BCDEF", "GH", "I"]);
+
+        // Direcionado ignora a direção
+        let mut graph: GraphPool<char> = GraphPool::directed();
+        let x = graph.add_vertex('X');
+        let y = graph.add_vertex('Y');
+        let z = graph.add_vertex('Z');
+        graph.add_edge(x, z, ());
+        graph.add_edge(y, z, ());
+        assert_eq!(graph.connected_components(), vec![vec![x, y, z]]);
+    }
+
+    #[test]
+    pub fn graph_minimum_spanning_tree() {
+        let (mut graph, [a, b, c, d, e, f]) = fixture();
+
+        let kruskal = graph.minimum_spanning_tree_kruskal().unwrap();
+        assert_eq!(kruskal, vec![(a, b, 1), (a, e, 2), (b, d, 3), (b, c, 4), (c, f, 6)]);
+
+        let prim = graph.minimum_spanning_tree_prim().unwrap();
+        assert_eq!(prim.len(), 5);
+        assert_eq!(prim.iter().map(|(_, _, w)| w).sum::<u32>(), 16);
+
+        // Floresta quando não é conexo
+        let g = graph.add_vertex('G');
+        let h = graph.add_vertex('H');
+        graph.add_edge(g, h, 10);
+        graph.add_edge(c, d, 0);
+        let kruskal = graph.minimum_spanning_tree_kruskal().unwrap();
+        let prim = graph.minimum_spanning_tree_prim().unwrap();
+        assert_eq!(kruskal.len(), 6);
+        assert_eq!(prim.len(), 6);
+        assert_eq!(kruskal.iter().map(|(_, _, w)| w).sum::<u32>(), 22);
+        assert_eq!(prim.iter().map(|(_, _, w)| w).sum::<u32>(), 22);
+        assert_eq!(kruskal[0], (c, d, 0));
+
+        // Somente em grafo não direcionado
+        let mut graph: GraphPool<char, u32> = GraphPool::directed();
+        let x = graph.add_vertex('X');
+        let y = graph.add_vertex('Y');
+        graph.add_edge(x, y, 1);
+        assert_eq!(graph.minimum_spanning_tree_kruskal(), None);
+        assert_eq!(graph.minimum_spanning_tree_prim(), None);
+    }
+
+    #[test]
+    pub fn graph_bipartite() {
+        // Triângulo B, C, D
+        let (mut graph, [_, _, c, d, e, f]) = fixture();
+        assert!(!graph.is_bipartite());
+
+        // Sem c-d ainda tem o ciclo A B C F E de tamanho 5
+        graph.remove_edge(c, d);
+        assert_eq!(graph.bipartition(), None);
+
+        // Sem f-e fica uma árvore
+        graph.remove_edge(f, e);
+        let (left, right) = graph.bipartition().unwrap();
+        assert_eq!(names(&graph, &left), "ACD");
+        assert_eq!(names(&graph, &right), "BEF");
+
+        // Direcionado: a direção não importa
+        let mut graph: GraphPool<char> = GraphPool::directed();
+        let x = graph.add_vertex('X');
+        let y = graph.add_vertex('Y');
+        let z = graph.add_vertex('Z');
+        graph.add_edge(x, y, ());
+        graph.add_edge(z, y, ());
+        assert_eq!(graph.bipartition(), Some((vec![x, z], vec![y])));
+        graph.add_edge(z, x, ());
+        assert!(!graph.is_bipartite());
+    }
+}
@@ -0,0 +1,281 @@
+use std::{collections::{HashMap, HashSet}, error::Error, fmt::{self, Display, Write}, str::FromStr};
+
+use super::{GraphPool, LinkedStack};
+
+/**
+ * Erro ao ler uma lista de arestas, line começa em 1
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeListError {
+    // O terceiro campo não pôde ser convertido para o tipo do peso
+    InvalidWeight { line: usize, weight: String },
+    // Mais de 3 campos na linha
+    TooManyFields { line: usize }
+}
+
+impl Display for EdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EdgeListError::InvalidWeight { line, weight } => write!(f, "linha {}: peso inválido '{}'", line, weight),
+            EdgeListError::TooManyFields { line } => write!(f, "linha {}: esperado 'origem destino [peso]'", line),
+        }
+    }
+}
+
+impl Error for EdgeListError {}
+
+/**
+ * Escapa aspas e barras para usar dentro de "..." no DOT
+ */
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/**
+ * Exportar para o Graphviz (DOT), para visualizar com 'dot -Tsvg grafo.dot > grafo.svg' ou em sites como viz-js.com
+ *
+ * - Cada vértice vira um nó com o índice como id e o Display do valor como label
+ * - No não direcionado cada aresta aparece uma vez (a -- b), no direcionado a -> b
+ * - path: caminho retornado pelo GraphSearch::get_path, seus vértices e arestas ficam em vermelho
+ */
+impl<V: Display, E> GraphPool<V, E> {
+    pub fn to_dot(&self, path: Option<&LinkedStack<usize>>) -> String {
+        self.write_dot(path, |_| None)
+    }
+
+    fn write_dot<F: Fn(&E) -> Option<String>>(&self, path: Option<&LinkedStack<usize>>, edge_label: F) -> String {
+        // O caminho está com o destino no topo, então cada par (anterior, próximo) é (path[i+1], path[i])
+        let path: Vec<usize> = path.map(|path| path.iter().copied().collect()).unwrap_or_default();
+        let path_vertices: HashSet<usize> = path.iter().copied().collect();
+        let mut path_edges: HashSet<(usize, usize)> = HashSet::new();
+        for pair in path.windows(2) {
+            path_edges.insert((pair[1], pair[0]));
+            if !self.is_directed() {
+                path_edges.insert((pair[0], pair[1]));
+            }
+        }
+
+        let (kind, arrow) = if self.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+        let mut ret = String::new();
+        writeln!(ret, "{} {{", kind).unwrap();
+        for (v, value) in self.vertices() {
+            write!(ret, "    {} [label=\"{}\"", v, dot_escape(&value.to_string())).unwrap();
+            if path_vertices.contains(&v) {
+                write!(ret, ", color=red").unwrap();
+            }
+            writeln!(ret, "];").unwrap();
+        }
+        for (a, _) in self.vertices() {
+            for (b, weight) in self.neighbors(a) {
+                // No não direcionado a aresta está guardada nos dois vértices
+                if !self.is_directed() && a > b { continue; }
+
+                let mut attributes = Vec::new();
+                if let Some(label) = edge_label(weight) {
+                    attributes.push(format!("label=\"{}\"", dot_escape(&label)));
+                }
+                if path_edges.contains(&(a, b)) {
+                    attributes.push(String::from("color=red, penwidth=2"));
+                }
+
+                write!(ret, "    {} {} {}", a, arrow, b).unwrap();
+                if !attributes.is_empty() {
+                    write!(ret, " [{}]", attributes.join(", ")).unwrap();
+                }
+                writeln!(ret, ";").unwrap();
+            }
+        }
+        ret.push('}');
+        return ret;
+    }
+
+    /**
+     * Lista de arestas, uma por linha 'origem destino', vértices sem arestas sozinhos na linha
+     * (o Display dos valores não pode ter espaços para conseguir ler de volta com add_edge_list)
+     */
+    pub fn to_edge_list(&self) -> String {
+        self.write_edge_list(|_| None)
+    }
+
+    fn write_edge_list<F: Fn(&E) -> Option<String>>(&self, edge_label: F) -> String {
+        let mut ret = String::new();
+        for (a, value) in self.vertices() {
+            let mut has_edges = self.in_degree(a) > 0;
+            for (b, weight) in self.neighbors(a) {
+                has_edges = true;
+                if !self.is_directed() && a > b { continue; }
+
+                write!(ret, "{} {}", value, self.get(b)).unwrap();
+                if let Some(label) = edge_label(weight) {
+                    write!(ret, " {}", label).unwrap();
+                }
+                ret.push('\n');
+            }
+            if !has_edges {
+                writeln!(ret, "{}", value).unwrap();
+            }
+        }
+        return ret;
+    }
+}
+
+impl<V: Display, E: Display> GraphPool<V, E> {
+    /**
+     * Igual ao to_dot, com o peso de cada aresta como label
+     */
+    pub fn to_dot_weighted(&self, path: Option<&LinkedStack<usize>>) -> String {
+        self.write_dot(path, |weight| Some(weight.to_string()))
+    }
+
+    /**
+     * Igual ao to_edge_list, com o peso no terceiro campo 'origem destino peso'
+     */
+    pub fn to_edge_list_weighted(&self) -> String {
+        self.write_edge_list(|weight| Some(weight.to_string()))
+    }
+}
+
+impl<E: FromStr + Default + Clone> GraphPool<String, E> {
+    /**
+     * Lê uma lista de arestas, útil para montar grafos de teste a partir de strings:
+     *
+     * # comentário até o final da linha
+     * A B 3    aresta A -> B com peso 3 (A -- B no não direcionado)
+     * B C      aresta sem peso, usa E::default()
+     * D        vértice sem arestas
+     *
+     * Vértices são encontrados pelo nome, criados na ordem em que aparecem se ainda não existirem
+     * Se alguma linha for inválida retorna o erro sem alterar o grafo
+     * (o peso precisa de FromStr, então não funciona com E = (), usar um número como u8)
+     */
+    pub fn add_edge_list(&mut self, text: &str) -> Result<(), EdgeListError> {
+        // Lê todas as linhas antes de mexer no grafo, assim um erro não deixa o grafo pela metade
+        let mut lines: Vec<(&str, Option<(&str, E)>)> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap();
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [] => {},
+                [name] => { lines.push((name, None)); },
+                [a, b] => { lines.push((a, Some((b, E::default())))); },
+                [a, b, weight] => {
+                    let weight = weight.parse::<E>().map_err(|_| EdgeListError::InvalidWeight { line: i + 1, weight: weight.to_string() })?;
+                    lines.push((a, Some((b, weight))));
+                },
+                _ => { return Err(EdgeListError::TooManyFields { line: i + 1 }); },
+            }
+        }
+
+        let mut by_name: HashMap<String, usize> = self.vertices().map(|(v, name)| (name.clone(), v)).collect();
+        let mut vertex = |graph: &mut GraphPool<String, E>, name: &str| -> usize {
+            *by_name.entry(name.to_string()).or_insert_with(|| graph.add_vertex(name.to_string()))
+        };
+        for (a, edge) in lines {
+            let a = vertex(self, a);
+            if let Some((b, weight)) = edge {
+                let b = vertex(self, b);
+                self.add_edge(a, b, weight);
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::estruturas::{graph::graph_fixture, GraphIter, GraphPool, GraphSearch};
+
+    use super::EdgeListError;
+
+    /**
+     * O graph_fixture em texto: "A B 1\nA E 2\nB D 3\n..."
+     */
+    fn fixture_text() -> String {
+        return graph_fixture().0.to_edge_list_weighted();
+    }
+
+    fn find(graph: &GraphPool<String, u32>, name: &str) -> usize {
+        graph.vertices().find(|(_, v)| *v == name).unwrap().0
+    }
+
+    #[test]
+    pub fn graph_edge_list() {
+        let mut graph: GraphPool<String, u32> = GraphPool::undirected();
+        graph.add_edge_list(&fixture_text()).unwrap();
+        assert_eq!(graph.vertex_count(), 6);
+        assert_eq!(graph.edge_count(), 7);
+        assert_eq!(graph.edge(find(&graph, "F"), find(&graph, "C")), Some(&6));
+
+        let mut visited = String::new();
+        let mut iter_state = GraphIter::breadth_first(find(&graph, "B"));
+        while let Some(node_index) = iter_state.next() {
+            visited.push_str(graph.visit(node_index, &mut iter_state));
+        }
+        assert_eq!(visited, String::from("BADCEF"));
+
+        // Continua no mesmo grafo, reaproveitando os vértices pelo nome
+        graph.add_edge_list("F G\nH # sozinho").unwrap();
+        assert_eq!(graph.vertex_count(), 8);
+        assert_eq!(graph.edge(find(&graph, "G"), find(&graph, "F")), Some(&0));
+        assert_eq!(graph.degree(find(&graph, "H")), 0);
+
+        // Ida e volta
+        let text = graph.to_edge_list_weighted();
+        assert_eq!(text, "A B 1\nA E 2\nB D 3\nB C 4\nE F 7\nD C 5\nC F 6\nF G 0\nH\n");
+        let mut copia: GraphPool<String, u32> = GraphPool::undirected();
+        copia.add_edge_list(&text).unwrap();
+        assert_eq!(copia.to_edge_list_weighted(), text);
+
+        // Sem o peso ao exportar
+        let mut graph: GraphPool<String, u8> = GraphPool::directed();
+        graph.add_edge_list("X Y 1\nY X\nZ").unwrap();
+        assert_eq!(graph.to_edge_list(), "X Y\nY X\nZ\n");
+
+        let mut graph: GraphPool<String, u32> = GraphPool::directed();
+        assert_eq!(graph.add_edge_list("A B 1\nA C x"), Err(EdgeListError::InvalidWeight { line: 2, weight: String::from("x") }));
+        assert_eq!(graph.add_edge_list("A B 1 2").unwrap_err().to_string(), "linha 1: esperado 'origem destino [peso]'");
+        // Com erro nenhuma linha é aplicada, nem as anteriores
+        assert_eq!(graph.vertex_count(), 0);
+        graph.add_edge_list("A B 1").unwrap();
+        assert_eq!(graph.add_edge_list("B C 2\nC D 3 4"), Err(EdgeListError::TooManyFields { line: 2 }));
+        assert_eq!(graph.to_edge_list_weighted(), "A B 1\n");
+    }
+
+    #[test]
+    pub fn graph_dot() {
+        let mut graph: GraphPool<String, u32> = GraphPool::directed();
+        graph.add_edge_list("A B 1\nB C 2\nA C 5\nD").unwrap();
+        *graph.get_mut(3) = String::from("\"D\"");
+        assert_eq!(graph.to_dot(None), "digraph {
+    0 [label=\"A\"];
+    1 [label=\"B\"];
+    2 [label=\"C\"];
+    3 [label=\"\\\"D\\\"\"];
+    0 -> 1;
+    0 -> 2;
+    1 -> 2;
+}");
+
+        // Destaca o caminho da busca
+        let mut graph: GraphPool<String, u32> = GraphPool::undirected();
+        graph.add_edge_list(&fixture_text()).unwrap();
+        let (b, f) = (find(&graph, "B"), find(&graph, "F"));
+        let mut iter_state = GraphSearch::breadth_first(b);
+        while let Some(path) = iter_state.next() {
+            let node_index = *path.peek().unwrap();
+            if node_index == f { break; }
+            graph.visit(node_index, &mut iter_state);
+        }
+        let path = iter_state.get_path();
+
+        let dot = graph.to_dot_weighted(Some(&path));
+        assert!(dot.starts_with("graph {\n"));
+        assert!(dot.contains(&format!("    {} [label=\"B\", color=red];", b)));
+        assert!(dot.contains(&format!("    {} [label=\"A\"];", find(&graph, "A"))));
+        assert!(dot.contains(&format!("    {} -- {} [label=\"4\", color=red, penwidth=2];", b, find(&graph, "C"))));
+        assert!(dot.contains(&format!("    {} -- {} [label=\"6\", color=red, penwidth=2];", find(&graph, "C"), f)));
+        assert!(dot.contains(&format!("    {} -- {} [label=\"1\"];", find(&graph, "A"), b)));
+        assert_eq!(dot.matches("--").count(), 7);
+        assert_eq!(dot.matches("color=red").count(), 5);
+    }
+}
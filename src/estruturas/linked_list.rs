@@ -1,4 +1,6 @@
-use super::{Queue, Stack, VecPool, NULL_INDEX};
+use std::{cmp::Ordering, fmt, mem, vec};
+
+use super::{CapacityOverflowError, Deque, PoolIndex, Queue, Stack, VecPool, NULL_INDEX};
 
 /**
  * Lista duplamente encadeada (Safe) sem o uso de ponteiros (isso mesmo nada de Box, Rc, Cell, unsafe, ...)
@@ -30,26 +32,26 @@ use super::{Queue, Stack, VecPool, NULL_INDEX};
  * When allocating a free node from the array, grab the firstEmptyIndex node, update the firstEmptyIndex as next index of the current array index (do not forget to update the next index as Null or empty or whatever after this).
  * When deallocating, update the next index of the deallocating node as firstEmptyIndex, then do firstEmptyIndex = deallocating node index.
  * In this way you create yourself a shortcut for allocating free nodes from the array.
+ * 
+ * Como sugerido acima, o tipo dos índices I pode ser u16 ou u32 (padrão usize), cada tipo tem seu próprio NULL (o maior valor).
+ * Com LinkedList<T, u16> as ligações next/prev ocupam 4 bytes por nó ao invés de 16, mas cabem no máximo 65535 nós
  */
-pub struct LinkedList<T> {
-    arr: VecPool<LinkedNode<T>>,
-    first: usize,
-    last: usize
+pub struct LinkedList<T, I = usize> {
+    arr: VecPool<LinkedNode<T, I>, I>,
+    first: I,
+    last: I
 }
 
-struct LinkedNode<T> {
+#[derive(Clone)]
+struct LinkedNode<T, I = usize> {
     value: T,
-    next: usize,
-    prev: usize
+    next: I,
+    prev: I
 }
 
 impl<T> LinkedList<T> {
     pub fn new() -> LinkedList<T> {
-        LinkedList { 
-            arr: VecPool::new(),
-            first: NULL_INDEX,
-            last: NULL_INDEX
-        }
+        Self::new_indexed()
     }
 
     pub fn from<const N: usize>(values: [T; N]) -> LinkedList<T> {
@@ -59,70 +61,141 @@ impl<T> LinkedList<T> {
         }
         return ret;
     }
+}
+
+impl<T, I: PoolIndex> LinkedList<T, I> {
+    /**
+     * Mesmo que new(), mas com outro tipo de índice: LinkedList::<T, u16>::new_indexed()
+     */
+    pub fn new_indexed() -> LinkedList<T, I> {
+        LinkedList { 
+            arr: VecPool::new_indexed(),
+            first: I::NULL,
+            last: I::NULL
+        }
+    }
+
+    /**
+     * Quantidade máxima de nós que o tipo de índice consegue endereçar
+     */
+    pub fn max_len(&self) -> usize {
+        self.arr.max_capacity()
+    }
 
     pub fn clear(&mut self) {
         self.arr.clear();
-        self.first = NULL_INDEX;
-        self.last = NULL_INDEX;
+        self.first = I::NULL;
+        self.last = I::NULL;
     }
 
     pub fn len(&self) -> usize {
         self.arr.len()
     }
 
-    pub fn add_first(&mut self, value: T) -> usize {
+    /**
+     * Panic se a lista já tem max_len() nós, ver try_add_first
+     */
+    pub fn add_first(&mut self, value: T) -> I {
+        match self.try_add_first(value) {
+            Ok(new_node) => new_node,
+            Err(_) => panic!("LinkedList: capacidade máxima do índice atingida ({})", self.max_len()),
+        }
+    }
+
+    /**
+     * Mesmo que add_first, mas retorna erro (devolvendo o valor) se não cabe mais nenhum nó no tipo de índice
+     */
+    pub fn try_add_first(&mut self, value: T) -> Result<I, CapacityOverflowError<T>> {
         // Cria o novo nó no array e obtêm o índice dele
         let len = self.arr.len();
-        let new_node = self.arr.alloc_node(LinkedNode { value: value, next: NULL_INDEX, prev: NULL_INDEX });
+        let new_node = self.try_alloc(value)?;
         if len == 0 {
             // Agora início e fim da lista é ele
             self.first = new_node;
             self.last = new_node; 
         } else {
             // 1 - .next do novo nó deve apontar para o antigo início da lista
-            self.arr[new_node].next = self.first;
+            self.node_mut(new_node).next = self.first;
             // 2 - .prev do antigo início deve apontar para o novo nó
-            self.arr[self.first].prev = new_node;
+            self.node_mut(self.first).prev = new_node;
             // 3 - Agora o início da lista é o novo nó
             self.first = new_node;
         }
 
-        return new_node;
+        return Ok(new_node);
+    }
+
+    /**
+     * Panic se a lista já tem max_len() nós, ver try_add_last
+     */
+    pub fn add_last(&mut self, value: T) -> I {
+        match self.try_add_last(value) {
+            Ok(new_node) => new_node,
+            Err(_) => panic!("LinkedList: capacidade máxima do índice atingida ({})", self.max_len()),
+        }
     }
 
-    pub fn add_last(&mut self, value: T) -> usize {
+    /**
+     * Mesmo que add_last, mas retorna erro (devolvendo o valor) se não cabe mais nenhum nó no tipo de índice
+     */
+    pub fn try_add_last(&mut self, value: T) -> Result<I, CapacityOverflowError<T>> {
         // Cria o novo nó no array e obtêm o índice dele
         let len = self.arr.len();
-        let new_node = self.arr.alloc_node(LinkedNode { value: value, next: NULL_INDEX, prev: NULL_INDEX });
+        let new_node = self.try_alloc(value)?;
         if len == 0 {
             // Agora início e fim da lista é ele
             self.first = new_node;
             self.last = new_node; 
         } else {
             // 1 - .prev do novo nó deve apontar para o antigo final da lista
-            self.arr[new_node].prev = self.last;
+            self.node_mut(new_node).prev = self.last;
             // 2 - .next do antigo final deve apontar para o novo nó
-            self.arr[self.last].next = new_node;
+            self.node_mut(self.last).next = new_node;
             // 3 - Agora o final da lista é o novo nó
             self.last = new_node;
         }
 
-        return new_node;
+        return Ok(new_node);
+    }
+
+    /**
+     * Aloca um nó desconectado no pool e retorna o índice dele já no tipo I
+     */
+    fn try_alloc(&mut self, value: T) -> Result<I, CapacityOverflowError<T>> {
+        match self.arr.try_alloc_node(LinkedNode { value: value, next: I::NULL, prev: I::NULL }) {
+            Ok(handle) => Ok(I::from_usize(handle.index()).unwrap()),
+            Err(err) => Err(CapacityOverflowError { value: err.value.value }),
+        }
+    }
+
+    fn alloc(&mut self, value: T) -> I {
+        match self.try_alloc(value) {
+            Ok(new_node) => new_node,
+            Err(_) => panic!("LinkedList: capacidade máxima do índice atingida ({})", self.max_len()),
+        }
+    }
+
+    fn node(&self, node: I) -> &LinkedNode<T, I> {
+        &self.arr[node.to_usize()]
+    }
+
+    fn node_mut(&mut self, node: I) -> &mut LinkedNode<T, I> {
+        &mut self.arr[node.to_usize()]
     }
 
     pub fn remove_first(&mut self) -> Option<T> {
-        if self.first == NULL_INDEX { return None; }
+        if self.first == I::NULL { return None; }
     
         // MOVE o valor do primeiro nó, deixando None no lugar
         let len = self.arr.len();
-        let first_node = self.arr.free_node(self.first).unwrap();
+        let first_node = self.arr.free_node(self.first.to_usize()).unwrap();
         if len == 1 {
             // Se era o último nó, limpa a lista
             self.clear();
         } else {
             // 1 - .prev do próximo nó deve apontar para NULL agora
             let next_index = first_node.next;
-            self.arr[next_index].prev = NULL_INDEX;
+            self.node_mut(next_index).prev = I::NULL;
             // 2 - first é o próximo do primeiro agora
             self.first = next_index;
         }
@@ -131,18 +204,18 @@ impl<T> LinkedList<T> {
     }
 
     pub fn remove_last(&mut self) -> Option<T> {
-        if self.last == NULL_INDEX { return None; }
+        if self.last == I::NULL { return None; }
     
         // MOVE o valor do último nó, deixando None no lugar
         let len = self.arr.len();
-        let last_node = self.arr.free_node(self.last).unwrap();
+        let last_node = self.arr.free_node(self.last.to_usize()).unwrap();
         if len == 1 {
             // Se era o último nó, limpa a lista
             self.clear();
         } else {
             // 1 - .next do nó anterior ao último deve apontar para NULL agora
             let prev_index = last_node.prev;
-            self.arr[prev_index].next = NULL_INDEX;
+            self.node_mut(prev_index).next = I::NULL;
             // 2 - last é o anterior ao último agora
             self.last = prev_index;
         }
@@ -150,19 +223,41 @@ impl<T> LinkedList<T> {
         return Some(last_node.value);
     }
 
-    pub fn index_first(&self) -> usize {
+    /**
+     * Compacta o pool de nós, removendo os espaços vazios e reescrevendo as ligações next/prev
+     * 
+     * Os índices retornados anteriormente pelos métodos add mudam, retorna o mapeamento antigo -> novo
+     * (mesmo formato de VecPool::compact, mas já no tipo I) para quem estiver guardando algum índice.
+     */
+    pub fn compact(&mut self) -> Vec<I> {
+        let remap: Vec<I> = self.arr.compact().into_iter()
+            .map(|index| if index == NULL_INDEX { I::NULL } else { I::from_usize(index).unwrap() })
+            .collect();
+        let relink = |index: I| if index == I::NULL { I::NULL } else { remap[index.to_usize()] };
+
+        for node in self.arr.values_mut() {
+            node.next = relink(node.next);
+            node.prev = relink(node.prev);
+        }
+        self.first = relink(self.first);
+        self.last = relink(self.last);
+
+        return remap;
+    }
+
+    pub fn index_first(&self) -> I {
         self.first
     }
 
-    pub fn index_last(&self) -> usize {
+    pub fn index_last(&self) -> I {
         self.last
     }
 
     /**
      * Obtêm referência imutável (deve informar o índice retornado pelo método add, NÃO é a posição na lista)
      */
-    pub fn get(&self, node: usize) -> Option<&T> {
-        return match self.arr.get_node(node) {
+    pub fn get(&self, node: I) -> Option<&T> {
+        return match self.arr.get_node(node.to_usize()) {
             Some(link_node) => Some(&link_node.value),
             None => None,
         }
@@ -171,57 +266,740 @@ impl<T> LinkedList<T> {
     /**
      * Obtêm referência mutável (deve informar o índice retornado pelo método add, NÃO é a posição na lista)
      */
-    pub fn get_mut(&mut self, node: usize) -> Option<&mut T> {
-        return match self.arr.get_mut_node(node) {
+    pub fn get_mut(&mut self, node: I) -> Option<&mut T> {
+        return match self.arr.get_mut_node(node.to_usize()) {
             Some(link_node) => Some(&mut link_node.value),
             None => None,
         }
     }
 
-    pub fn iter(&self) -> LinkedListIter<T> {
+    pub fn iter(&self) -> LinkedListIter<'_, T, I> {
         LinkedListIter { 
             pool: &self.arr,
-            atual: self.arr.get_node(self.first)
+            front: self.first,
+            back: self.last,
+            remaining: self.len()
+        }
+    }
+
+    /**
+     * Atravessa com referências mutáveis, na ordem da lista
+     * 
+     * Sem unsafe não tem como emprestar vários &mut do pool seguindo os índices next,
+     * então primeiro separa os &mut de cada nó (na ordem do pool) e depois ordena eles seguindo a lista
+     */
+    pub fn iter_mut(&mut self) -> LinkedListIterMut<'_, T> {
+        let len = self.arr.len();
+        let mut atual = self.first;
+        let mut nodes: Vec<Option<&mut LinkedNode<T, I>>> = Vec::new();
+        nodes.resize_with(self.arr.arr_len(), || None);
+        for (handle, node) in self.arr.iter_mut() {
+            nodes[handle.index()] = Some(node);
+        }
+
+        let mut values = Vec::with_capacity(len);
+        while atual != I::NULL {
+            let node = nodes[atual.to_usize()].take().unwrap();
+            atual = node.next;
+            values.push(&mut node.value);
+        }
+
+        LinkedListIterMut { inner: values.into_iter() }
+    }
+
+    /**
+     * Remove o nó em O(1) (deve informar o índice retornado pelo método add, NÃO é a posição na lista)
+     */
+    pub fn remove(&mut self, node: I) -> Option<T> {
+        self.arr.get_node(node.to_usize())?;
+
+        self.unlink(node);
+        let removed = self.arr.free_node(node.to_usize()).unwrap();
+        return Some(removed.value);
+    }
+
+    /**
+     * Insere um novo valor logo depois do nó informado, retorna o índice do novo nó
+     * Panic se a lista já tem max_len() nós, ver try_insert_after
+     */
+    pub fn insert_after(&mut self, node: I, value: T) -> Option<I> {
+        self.arr.get_node(node.to_usize())?;
+
+        let new_node = self.alloc(value);
+        self.link_after(node, new_node);
+        return Some(new_node);
+    }
+
+    /**
+     * Mesmo que insert_after, mas retorna erro (devolvendo o valor) se não cabe mais nenhum nó no tipo de índice
+     */
+    pub fn try_insert_after(&mut self, node: I, value: T) -> Result<Option<I>, CapacityOverflowError<T>> {
+        if self.arr.get_node(node.to_usize()).is_none() { return Ok(None); }
+
+        let new_node = self.try_alloc(value)?;
+        self.link_after(node, new_node);
+        return Ok(Some(new_node));
+    }
+
+    /**
+     * Insere um novo valor logo antes do nó informado, retorna o índice do novo nó
+     * Panic se a lista já tem max_len() nós, ver try_insert_before
+     */
+    pub fn insert_before(&mut self, node: I, value: T) -> Option<I> {
+        self.arr.get_node(node.to_usize())?;
+
+        let new_node = self.alloc(value);
+        self.link_before(node, new_node);
+        return Some(new_node);
+    }
+
+    /**
+     * Mesmo que insert_before, mas retorna erro (devolvendo o valor) se não cabe mais nenhum nó no tipo de índice
+     */
+    pub fn try_insert_before(&mut self, node: I, value: T) -> Result<Option<I>, CapacityOverflowError<T>> {
+        if self.arr.get_node(node.to_usize()).is_none() { return Ok(None); }
+
+        let new_node = self.try_alloc(value)?;
+        self.link_before(node, new_node);
+        return Ok(Some(new_node));
+    }
+
+    /**
+     * Move o nó para o início da lista, sem mudar o índice dele (útil para cache LRU)
+     */
+    pub fn move_to_front(&mut self, node: I) -> bool {
+        if self.arr.get_node(node.to_usize()).is_none() { return false; }
+        if node == self.first { return true; }
+
+        self.unlink(node);
+        let first = self.first;
+        self.link_before(first, node);
+        return true;
+    }
+
+    /**
+     * Move o nó para o final da lista, sem mudar o índice dele
+     */
+    pub fn move_to_back(&mut self, node: I) -> bool {
+        if self.arr.get_node(node.to_usize()).is_none() { return false; }
+        if node == self.last { return true; }
+
+        self.unlink(node);
+        let last = self.last;
+        self.link_after(last, node);
+        return true;
+    }
+
+    /**
+     * Desconecta o nó da lista, ligando o anterior com o próximo. Não libera o nó do pool
+     */
+    fn unlink(&mut self, node: I) {
+        let LinkedNode { prev, next, .. } = *self.node(node);
+
+        if prev == I::NULL { self.first = next; } else { self.node_mut(prev).next = next; }
+        if next == I::NULL { self.last = prev; } else { self.node_mut(next).prev = prev; }
+
+        self.node_mut(node).prev = I::NULL;
+        self.node_mut(node).next = I::NULL;
+    }
+
+    /**
+     * Conecta new_node (já alocado e desconectado) logo depois de node
+     */
+    fn link_after(&mut self, node: I, new_node: I) {
+        let next = self.node(node).next;
+        // 1 - novo nó fica entre node e o próximo
+        self.node_mut(new_node).prev = node;
+        self.node_mut(new_node).next = next;
+        // 2 - node e o próximo apontam para o novo nó
+        self.node_mut(node).next = new_node;
+        if next == I::NULL { self.last = new_node; } else { self.node_mut(next).prev = new_node; }
+    }
+
+    /**
+     * Conecta new_node (já alocado e desconectado) logo antes de node
+     */
+    fn link_before(&mut self, node: I, new_node: I) {
+        let prev = self.node(node).prev;
+        // 1 - novo nó fica entre o anterior e node
+        self.node_mut(new_node).prev = prev;
+        self.node_mut(new_node).next = node;
+        // 2 - o anterior e node apontam para o novo nó
+        self.node_mut(node).prev = new_node;
+        if prev == I::NULL { self.first = new_node; } else { self.node_mut(prev).next = new_node; }
+    }
+
+    /**
+     * Move todos os nós de other para o final desta lista, other fica vazia
+     * 
+     * Os nós são realocados no pool desta lista, então os índices mudam.
+     * Retorna o mapeamento antigo (em other) -> novo (nesta lista), mesmo formato de compact
+     * Panic se as duas juntas passam de max_len() nós, ver try_append
+     */
+    pub fn append(&mut self, other: &mut LinkedList<T, I>) -> Vec<I> {
+        match self.try_append(other) {
+            Ok(remap) => remap,
+            Err(_) => panic!("LinkedList: capacidade máxima do índice atingida ({})", self.max_len()),
+        }
+    }
+
+    /**
+     * Mesmo que append, mas retorna erro se as duas juntas passam de max_len() nós.
+     * A verificação é feita antes de mover qualquer nó, então no erro other continua intacta
+     */
+    pub fn try_append(&mut self, other: &mut LinkedList<T, I>) -> Result<Vec<I>, CapacityOverflowError<()>> {
+        self.check_fits(other)?;
+
+        let (remap, first, last) = self.move_nodes(other);
+        if first != I::NULL {
+            let after = self.last;
+            self.link_range_after(after, first, last);
+        }
+        return Ok(remap);
+    }
+
+    /**
+     * Igual ao append, mas insere os nós de other logo depois de node (no meio da lista)
+     * Retorna None, sem mexer em other, se node não existe. Panic se não cabe, ver try_splice
+     */
+    pub fn splice(&mut self, node: I, other: &mut LinkedList<T, I>) -> Option<Vec<I>> {
+        match self.try_splice(node, other) {
+            Ok(remap) => remap,
+            Err(_) => panic!("LinkedList: capacidade máxima do índice atingida ({})", self.max_len()),
+        }
+    }
+
+    /**
+     * Mesmo que splice, mas retorna erro (sem mexer em other) se as duas juntas passam de max_len() nós
+     */
+    pub fn try_splice(&mut self, node: I, other: &mut LinkedList<T, I>) -> Result<Option<Vec<I>>, CapacityOverflowError<()>> {
+        if self.arr.get_node(node.to_usize()).is_none() { return Ok(None); }
+        self.check_fits(other)?;
+
+        let (remap, first, last) = self.move_nodes(other);
+        if first != I::NULL {
+            self.link_range_after(node, first, last);
+        }
+        return Ok(Some(remap));
+    }
+
+    /**
+     * Os espaços vazios são reaproveitados antes de crescer o pool, então basta o total de nós caber no índice
+     */
+    fn check_fits(&self, other: &LinkedList<T, I>) -> Result<(), CapacityOverflowError<()>> {
+        if self.len() + other.len() > self.max_len() {
+            return Err(CapacityOverflowError { value: () });
+        }
+        return Ok(());
+    }
+
+    /**
+     * Copia os nós de other para o pool desta lista, ligados entre si mas ainda fora desta lista. other fica vazia
+     * (deve ser verificado antes com check_fits, a cópia não pode falhar no meio)
+     * Retorna o mapeamento e os novos índices do primeiro e último copiados (NULL se other estava vazia)
+     */
+    fn move_nodes(&mut self, other: &mut LinkedList<T, I>) -> (Vec<I>, I, I) {
+        let mut remap = vec![I::NULL; other.arr.arr_len()];
+        let other_first = other.first;
+        let other_last = other.last;
+        other.first = I::NULL;
+        other.last = I::NULL;
+        if other_first == I::NULL { return (remap, I::NULL, I::NULL); }
+
+        // 1 - Copiar os nós para o pool desta lista, ainda com as ligações antigas
+        self.arr.reserve(other.arr.len());
+        for (handle, node) in other.arr.drain() {
+            remap[handle.index()] = I::from_usize(self.arr.alloc_node(node).index()).unwrap();
+        }
+
+        // 2 - Reescrever as ligações dos nós copiados
+        let relink = |index: I| if index == I::NULL { I::NULL } else { remap[index.to_usize()] };
+        let mut atual = remap[other_first.to_usize()];
+        while atual != I::NULL {
+            let node = self.node_mut(atual);
+            node.prev = relink(node.prev);
+            node.next = relink(node.next);
+            atual = node.next;
         }
+
+        let (first, last) = (remap[other_first.to_usize()], remap[other_last.to_usize()]);
+        return (remap, first, last);
+    }
+
+    /**
+     * Conecta a sequência first..last (já ligada entre si) logo depois de node, ou no início se node é NULL
+     */
+    fn link_range_after(&mut self, node: I, first: I, last: I) {
+        let next = if node == I::NULL { self.first } else { self.node(node).next };
+        self.node_mut(first).prev = node;
+        self.node_mut(last).next = next;
+        if node == I::NULL { self.first = first; } else { self.node_mut(node).next = first; }
+        if next == I::NULL { self.last = last; } else { self.node_mut(next).prev = last; }
+    }
+
+    /**
+     * Separa a lista em duas: esta fica com os nós antes de node, e retorna uma nova com node até o final
+     * Os valores movidos recebem novos índices na nova lista
+     */
+    pub fn split_off(&mut self, node: I) -> Option<LinkedList<T, I>> {
+        self.arr.get_node(node.to_usize())?;
+
+        // A nova lista nunca tem mais nós do que esta, então add_first não passa do limite do índice
+
+        let mut ret = LinkedList::new_indexed();
+        loop {
+            let atual = self.last;
+            ret.add_first(self.remove_last().unwrap());
+            if atual == node {
+                break;
+            }
+        }
+        return Some(ret);
+    }
+
+    /**
+     * Inverte a ordem da lista em O(n), só trocando prev e next de cada nó (os índices não mudam)
+     */
+    pub fn reverse(&mut self) {
+        for node in self.arr.values_mut() {
+            mem::swap(&mut node.prev, &mut node.next);
+        }
+        mem::swap(&mut self.first, &mut self.last);
+    }
+
+    pub fn sort(&mut self) 
+    where 
+        T: Ord
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    /**
+     * Merge sort estável, bottom-up, sem recursão
+     * 
+     * Só reescreve as ligações next/prev, os valores não são movidos e os índices continuam válidos.
+     * - Em cada passada junta pares de sub-listas de tamanho 'size' (1, 2, 4, ...)
+     * - Durante a ordenação só next é usado, no final prev é reconstruído
+     */
+    pub fn sort_by<F>(&mut self, mut compare: F) 
+    where 
+        F: FnMut(&T, &T) -> Ordering
+    {
+        let len = self.len();
+        if len < 2 { return; }
+
+        let mut head = self.first;
+        let mut size = 1;
+        while size < len {
+            let mut new_head = I::NULL;
+            let mut tail = I::NULL;
+            let mut atual = head;
+            while atual != I::NULL {
+                let left = atual;
+                let right = self.cut_after(left, size);
+                atual = self.cut_after(right, size);
+
+                let (merged_head, merged_tail) = self.merge(left, right, &mut compare);
+                if tail == I::NULL { new_head = merged_head; } else { self.node_mut(tail).next = merged_head; }
+                tail = merged_tail;
+            }
+            head = new_head;
+            size *= 2;
+        }
+
+        // Reconstruir prev, first e last
+        let mut prev = I::NULL;
+        let mut atual = head;
+        while atual != I::NULL {
+            self.node_mut(atual).prev = prev;
+            prev = atual;
+            atual = self.node(atual).next;
+        }
+        self.first = head;
+        self.last = prev;
+    }
+
+    /**
+     * Avança n nós a partir de start e corta a ligação next ali, retorna o início do resto (ou NULL)
+     */
+    fn cut_after(&mut self, start: I, n: usize) -> I {
+        let mut atual = start;
+        for _ in 1..n {
+            if atual == I::NULL { break; }
+            atual = self.node(atual).next;
+        }
+        if atual == I::NULL { return I::NULL; }
+
+        let rest = self.node(atual).next;
+        self.node_mut(atual).next = I::NULL;
+        return rest;
+    }
+
+    /**
+     * Junta duas sub-listas já ordenadas (ligadas só por next), retorna (início, fim)
+     * Em caso de empate pega da esquerda primeiro, por isso é estável
+     */
+    fn merge<F>(&mut self, mut left: I, mut right: I, compare: &mut F) -> (I, I) 
+    where 
+        F: FnMut(&T, &T) -> Ordering
+    {
+        let mut head = I::NULL;
+        let mut tail = I::NULL;
+        while left != I::NULL || right != I::NULL {
+            let take_left = if left == I::NULL { 
+                false 
+            } else if right == I::NULL { 
+                true 
+            } else { 
+                compare(&self.node(right).value, &self.node(left).value) != Ordering::Less 
+            };
+
+            let atual = if take_left { left } else { right };
+            if take_left { left = self.node(left).next; } else { right = self.node(right).next; }
+
+            if tail == I::NULL { head = atual; } else { self.node_mut(tail).next = atual; }
+            tail = atual;
+        }
+        self.node_mut(tail).next = I::NULL;
+        return (head, tail);
+    }
+
+    pub fn cursor_front(&self) -> Cursor<'_, T, I> {
+        Cursor { list: self, atual: self.first }
+    }
+
+    pub fn cursor_back(&self) -> Cursor<'_, T, I> {
+        Cursor { list: self, atual: self.last }
+    }
+
+    /**
+     * Cursor posicionado no nó informado (se não existir, fica na posição 'fantasma', fora da lista)
+     */
+    pub fn cursor_at(&self, node: I) -> Cursor<'_, T, I> {
+        let atual = if self.arr.get_node(node.to_usize()).is_some() { node } else { I::NULL };
+        Cursor { list: self, atual: atual }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, I> {
+        let atual = self.first;
+        CursorMut { list: self, atual: atual }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, I> {
+        let atual = self.last;
+        CursorMut { list: self, atual: atual }
+    }
+
+    pub fn cursor_at_mut(&mut self, node: I) -> CursorMut<'_, T, I> {
+        let atual = if self.arr.get_node(node.to_usize()).is_some() { node } else { I::NULL };
+        CursorMut { list: self, atual: atual }
     }
 }
 
-impl<T> Stack<T> for LinkedList<T> {
+impl<T, I: PoolIndex> Stack<T> for LinkedList<T, I> {
     fn push(&mut self, value: T) { self.add_first(value); }
     fn pop(&mut self) -> Option<T> { return self.remove_first(); }
     fn peek(&self) -> Option<&T> { return self.get(self.first); }
 }
 
-impl<T> Queue<T> for LinkedList<T> {
+impl<T, I: PoolIndex> Queue<T> for LinkedList<T, I> {
     fn enqueue(&mut self, value: T) { self.add_last(value); }
     fn dequeue(&mut self) -> Option<T> { return self.remove_first(); }
     fn head(&self) -> Option<&T> { return self.get(self.first); }
     fn tail(&self) -> Option<&T> { return self.get(self.last); }
+    fn len(&self) -> usize { return self.arr.len(); }
+}
+
+impl<T, I: PoolIndex> Deque<T> for LinkedList<T, I> {
+    fn push_front(&mut self, value: T) { self.add_first(value); }
+    fn push_back(&mut self, value: T) { self.add_last(value); }
+    fn pop_front(&mut self) -> Option<T> { return self.remove_first(); }
+    fn pop_back(&mut self) -> Option<T> { return self.remove_last(); }
+    fn front(&self) -> Option<&T> { return self.get(self.first); }
+    fn back(&self) -> Option<&T> { return self.get(self.last); }
 }
 
-// 'a lifetime, atual deve viver tanto quanto a instância da struct
-pub struct LinkedListIter<'a,T> {
-    pool: &'a VecPool<LinkedNode<T>>,
-    atual: Option<&'a LinkedNode<T>>
+// 'a lifetime, o iterador deve viver tanto quanto a lista
+pub struct LinkedListIter<'a,T,I = usize> {
+    pool: &'a VecPool<LinkedNode<T, I>, I>,
+    front: I,
+    back: I,
+    remaining: usize
 }
 
-impl<'a,T> Iterator for LinkedListIter<'a,T> {
+impl<'a,T,I: PoolIndex> Iterator for LinkedListIter<'a,T,I> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(atual) = self.atual {
-            let ret: Option<&T> = Some(&atual.value);
+        if self.remaining == 0 { return None; }
 
-            self.atual = self.pool.get_node(atual.next);
-            ret
-        } else {
-            None
+        let atual = &self.pool[self.front.to_usize()];
+        self.front = atual.next;
+        self.remaining -= 1;
+        Some(&atual.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T,I: PoolIndex> DoubleEndedIterator for LinkedListIter<'_,T,I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 { return None; }
+
+        let atual = &self.pool[self.back.to_usize()];
+        self.back = atual.prev;
+        self.remaining -= 1;
+        Some(&atual.value)
+    }
+}
+
+impl<T,I: PoolIndex> ExactSizeIterator for LinkedListIter<'_,T,I> {}
+
+pub struct LinkedListIterMut<'a,T> {
+    inner: vec::IntoIter<&'a mut T>
+}
+
+impl<'a,T> Iterator for LinkedListIterMut<'a,T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for LinkedListIterMut<'_,T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListIterMut<'_,T> {}
+
+/**
+ * Iterador que consome a lista, removendo do início (ou do final com next_back)
+ */
+pub struct LinkedListIntoIter<T, I = usize> {
+    list: LinkedList<T, I>
+}
+
+impl<T, I: PoolIndex> Iterator for LinkedListIntoIter<T, I> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.remove_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T, I: PoolIndex> DoubleEndedIterator for LinkedListIntoIter<T, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.remove_last()
+    }
+}
+
+impl<T, I: PoolIndex> ExactSizeIterator for LinkedListIntoIter<T, I> {}
+
+impl<T, I: PoolIndex> IntoIterator for LinkedList<T, I> {
+    type Item = T;
+    type IntoIter = LinkedListIntoIter<T, I>;
+    fn into_iter(self) -> Self::IntoIter {
+        LinkedListIntoIter { list: self }
+    }
+}
+
+impl<'a,T,I: PoolIndex> IntoIterator for &'a LinkedList<T,I> {
+    type Item = &'a T;
+    type IntoIter = LinkedListIter<'a,T,I>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a,T,I: PoolIndex> IntoIterator for &'a mut LinkedList<T,I> {
+    type Item = &'a mut T;
+    type IntoIter = LinkedListIterMut<'a,T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// let list: LinkedList<_> = iter.collect();
+impl<T, I: PoolIndex> FromIterator<T> for LinkedList<T, I> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let mut ret = LinkedList::new_indexed();
+        ret.extend(iter);
+        return ret;
+    }
+}
+
+impl<T, I: PoolIndex> LinkedList<T, I> {
+    /**
+     * Mesmo que extend, mas para no primeiro valor que não cabe e o devolve no erro
+     * (os valores anteriores a ele continuam na lista)
+     */
+    pub fn try_extend<It: IntoIterator<Item = T>>(&mut self, iter: It) -> Result<(), CapacityOverflowError<T>> {
+        for value in iter {
+            self.try_add_last(value)?;
+        }
+        return Ok(());
+    }
+}
+
+// Panic se passar de max_len() nós, ver try_extend
+impl<T, I: PoolIndex> Extend<T> for LinkedList<T, I> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        for value in iter {
+            self.add_last(value);
         }
     }
 }
 
+impl<T: fmt::Debug, I: PoolIndex> fmt::Debug for LinkedList<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Clona o pool inteiro, então os índices dos nós continuam os mesmos na cópia
+impl<T: Clone, I: PoolIndex> Clone for LinkedList<T, I> {
+    fn clone(&self) -> Self {
+        LinkedList { 
+            arr: self.arr.clone(), 
+            first: self.first, 
+            last: self.last 
+        }
+    }
+}
+
+// Compara somente os valores na ordem da lista (não os índices)
+impl<T: PartialEq, I: PoolIndex> PartialEq for LinkedList<T, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, I: PoolIndex> Eq for LinkedList<T, I> {}
+
+/**
+ * Cursor para atravessar a lista nos dois sentidos a partir de qualquer nó
+ * 
+ * Assim como o CursorMut da std, existe uma posição 'fantasma' (NULL) entre o último e o primeiro:
+ * - move_next() no último vai para o fantasma, e move_next() no fantasma vai para o primeiro
+ * - move_prev() no primeiro vai para o fantasma, e move_prev() no fantasma vai para o último
+ */
+pub struct Cursor<'a, T, I = usize> {
+    list: &'a LinkedList<T, I>,
+    atual: I
+}
+
+impl<'a, T, I: PoolIndex> Cursor<'a, T, I> {
+    /**
+     * Índice do nó atual (I::NULL se está no fantasma)
+     */
+    pub fn index(&self) -> I {
+        self.atual
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.list.get(self.atual)
+    }
+
+    pub fn move_next(&mut self) {
+        self.atual = if self.atual == I::NULL { self.list.first } else { self.list.node(self.atual).next };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.atual = if self.atual == I::NULL { self.list.last } else { self.list.node(self.atual).prev };
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = if self.atual == I::NULL { self.list.first } else { self.list.node(self.atual).next };
+        self.list.get(next)
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = if self.atual == I::NULL { self.list.last } else { self.list.node(self.atual).prev };
+        self.list.get(prev)
+    }
+}
+
+/**
+ * Cursor que permite editar a lista no lugar: alterar o valor atual, inserir antes/depois e remover
+ */
+pub struct CursorMut<'a, T, I = usize> {
+    list: &'a mut LinkedList<T, I>,
+    atual: I
+}
+
+impl<T, I: PoolIndex> CursorMut<'_, T, I> {
+    pub fn index(&self) -> I {
+        self.atual
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.get_mut(self.atual)
+    }
+
+    pub fn move_next(&mut self) {
+        self.atual = if self.atual == I::NULL { self.list.first } else { self.list.node(self.atual).next };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.atual = if self.atual == I::NULL { self.list.last } else { self.list.node(self.atual).prev };
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = if self.atual == I::NULL { self.list.first } else { self.list.node(self.atual).next };
+        self.list.get_mut(next)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = if self.atual == I::NULL { self.list.last } else { self.list.node(self.atual).prev };
+        self.list.get_mut(prev)
+    }
+
+    /**
+     * Insere depois do atual (no fantasma, insere no início da lista). O cursor não se move
+     */
+    pub fn insert_after(&mut self, value: T) -> I {
+        if self.atual == I::NULL {
+            return self.list.add_first(value);
+        }
+        return self.list.insert_after(self.atual, value).unwrap();
+    }
+
+    /**
+     * Insere antes do atual (no fantasma, insere no final da lista). O cursor não se move
+     */
+    pub fn insert_before(&mut self, value: T) -> I {
+        if self.atual == I::NULL {
+            return self.list.add_last(value);
+        }
+        return self.list.insert_before(self.atual, value).unwrap();
+    }
+
+    /**
+     * Remove o nó atual e move o cursor para o próximo
+     */
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.atual == I::NULL { return None; }
+
+        let next = self.list.node(self.atual).next;
+        let ret = self.list.remove(self.atual);
+        self.atual = next;
+        return ret;
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::estruturas::{linked_list::*, run_queue_tests, run_stack_tests};
+    use crate::estruturas::{linked_list::*, run_deque_tests, run_queue_tests, run_stack_tests};
 
     #[test]
     pub fn list_methods() {
@@ -257,6 +1035,445 @@ mod test {
         assert_eq!(list.len(), 2);
     }
 
+    #[test]
+    pub fn list_compact() {
+        let mut list = LinkedList::new();
+        let mut nodes = Vec::new();
+        for c in ['A','B','C','D','E','F'] {
+            nodes.push(list.add_first(c));
+        }
+        // F E D C B A -> E D C B
+        list.remove_first();
+        list.remove_last();
+        assert_eq!(list.arr.arr_len(), 6);
+
+        let remap = list.compact();
+        assert_eq!(list.arr.arr_len(), 4);
+        assert_eq!(list.iter().collect::<String>(), "EDCB");
+        assert_eq!(list.get(remap[nodes[3]]), Some(&'D'));
+        assert_eq!(list.get(list.index_first()), Some(&'E'));
+        assert_eq!(list.get(list.index_last()), Some(&'B'));
+
+        // Continua funcionando normalmente depois de compactar
+        list.add_last('a');
+        list.add_first('f');
+        assert_eq!(list.iter().collect::<String>(), "fEDCBa");
+        assert_eq!(list.remove_last(), Some('a'));
+        assert_eq!(list.remove_first(), Some('f'));
+        assert_eq!(list.remove_first(), Some('E'));
+        assert_eq!(list.remove_last(), Some('B'));
+    }
+
+    #[test]
+    pub fn list_remove_insert() {
+        let mut list = LinkedList::from(['A','B','C']);
+        let a = list.index_first();
+        let c = list.index_last();
+        let b = list.insert_after(a, 'b').unwrap();
+        list.insert_before(c, 'c').unwrap();
+        assert_eq!(list.iter().collect::<String>(), "AbBcC");
+        assert_eq!(list.len(), 5);
+
+        assert_eq!(list.remove(b), Some('b'));
+        assert_eq!(list.remove(b), None);
+        assert_eq!(list.insert_after(b, '?'), None);
+        assert_eq!(list.iter().collect::<String>(), "ABcC");
+
+        // Remover as pontas atualiza first e last
+        assert_eq!(list.remove(a), Some('A'));
+        assert_eq!(list.remove(c), Some('C'));
+        assert_eq!(list.iter().collect::<String>(), "Bc");
+        assert_eq!(list.get(list.index_first()), Some(&'B'));
+        assert_eq!(list.get(list.index_last()), Some(&'c'));
+
+        let first = list.index_first();
+        let last = list.index_last();
+        list.insert_before(first, '<');
+        list.insert_after(last, '>');
+        assert_eq!(list.iter().collect::<String>(), "<Bc>");
+
+        while list.len() > 0 {
+            list.remove(list.index_last());
+        }
+        assert_eq!(list.index_first(), NULL_INDEX);
+        assert_eq!(list.index_last(), NULL_INDEX);
+        list.add_last('Z');
+        assert_eq!(list.iter().collect::<String>(), "Z");
+    }
+
+    #[test]
+    pub fn list_move_to_front() {
+        let mut list = LinkedList::new();
+        let nodes: Vec<_> = ['A','B','C','D'].into_iter().map(|c| list.add_last(c)).collect();
+
+        assert!(list.move_to_front(nodes[2]));
+        assert_eq!(list.iter().collect::<String>(), "CABD");
+        assert!(list.move_to_front(nodes[3]));
+        assert_eq!(list.iter().collect::<String>(), "DCAB");
+        assert!(list.move_to_front(nodes[3]));
+        assert_eq!(list.iter().collect::<String>(), "DCAB");
+        assert!(list.move_to_back(nodes[3]));
+        assert_eq!(list.iter().collect::<String>(), "CABD");
+
+        // O índice continua o mesmo
+        assert_eq!(list.get(nodes[3]), Some(&'D'));
+        assert_eq!(list.remove_last(), Some('D'));
+        assert!(!list.move_to_front(nodes[3]));
+        assert_eq!(list.remove_first(), Some('C'));
+        assert_eq!(list.remove_first(), Some('A'));
+        assert_eq!(list.remove_first(), Some('B'));
+    }
+
+    #[test]
+    pub fn list_cursor() {
+        let list = LinkedList::from(['A','B','C']);
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(&'A'));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some(&'B'));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&'C'));
+        // Fantasma entre o último e o primeiro
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), NULL_INDEX);
+        assert_eq!(cursor.peek_next(), Some(&'A'));
+        assert_eq!(cursor.peek_prev(), Some(&'C'));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&'C'));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&'B'));
+
+        let mut cursor = list.cursor_back();
+        let mut rev = String::new();
+        while let Some(c) = cursor.current() {
+            rev.push(*c);
+            cursor.move_prev();
+        }
+        assert_eq!(rev, "CBA");
+    }
+
+    #[test]
+    pub fn list_cursor_mut() {
+        let mut list = LinkedList::from(['a','b','c','d']);
+        let mut cursor = list.cursor_front_mut();
+        // Coloca em maiúsculo e remove o 'c'
+        while cursor.index() != NULL_INDEX {
+            if cursor.current() == Some(&mut 'c') {
+                assert_eq!(cursor.remove_current(), Some('c'));
+                continue;
+            }
+            cursor.current().unwrap().make_ascii_uppercase();
+            cursor.move_next();
+        }
+        assert_eq!(list.iter().collect::<String>(), "ABD");
+
+        let b = list.cursor_front().peek_next().copied();
+        assert_eq!(b, Some('B'));
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.insert_before('C');
+        cursor.insert_after('E');
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 'E'));
+        cursor.move_next();
+        // no fantasma insere nas pontas
+        cursor.insert_after('0');
+        cursor.insert_before('F');
+        assert_eq!(list.iter().collect::<String>(), "0ABCDEF");
+
+        let first = list.index_first();
+        let mut cursor = list.cursor_at_mut(first);
+        assert_eq!(cursor.remove_current(), Some('0'));
+        assert_eq!(cursor.current(), Some(&mut 'A'));
+        assert_eq!(list.len(), 6);
+    }
+
+    #[test]
+    pub fn list_iter_double_ended() {
+        let list = LinkedList::from(['A','B','C','D','E']);
+        assert_eq!(list.iter().len(), 5);
+        assert_eq!(list.iter().rev().collect::<String>(), "EDCBA");
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&'A'));
+        assert_eq!(iter.next_back(), Some(&'E'));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&'D'));
+        assert_eq!(iter.next(), Some(&'B'));
+        assert_eq!(iter.next(), Some(&'C'));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let empty: LinkedList<char> = LinkedList::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter().next_back(), None);
+    }
+
+    #[test]
+    pub fn list_iter_mut() {
+        let mut list = LinkedList::new();
+        list.add_last('b');
+        list.add_first('a');
+        list.add_last('c');
+        for value in list.iter_mut() {
+            value.make_ascii_uppercase();
+        }
+        assert_eq!(list.iter().collect::<String>(), "ABC");
+
+        for value in (&mut list).into_iter().rev().take(1) {
+            *value = 'Z';
+        }
+        assert_eq!(list.iter().collect::<String>(), "ABZ");
+        assert_eq!(list.iter_mut().len(), 3);
+    }
+
+    #[test]
+    pub fn list_into_iter() {
+        let list = LinkedList::from(['A','B','C','D']);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some('A'));
+        assert_eq!(iter.next_back(), Some('D'));
+        assert_eq!(iter.collect::<Vec<_>>(), vec!['B','C']);
+
+        let mut visited = String::new();
+        for value in &LinkedList::from(['x','y']) {
+            visited.push(*value);
+        }
+        assert_eq!(visited, "xy");
+    }
+
+    #[test]
+    pub fn list_traits() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        list.extend([4, 5]);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5]");
+
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(copy.get(list.index_last()), Some(&5));
+
+        list.remove_last();
+        assert_ne!(copy, list);
+
+        // Mesmos valores, mas com índices diferentes no pool, ainda são iguais
+        let mut other = LinkedList::new();
+        for value in [4, 3, 2, 1] {
+            other.add_first(value);
+        }
+        assert_eq!(other, list);
+
+        let sum: i32 = list.iter().map(|v| v * 10).filter(|v| *v > 10).sum();
+        assert_eq!(sum, 90);
+    }
+
+    #[test]
+    pub fn list_bulk_methods() {
+        let mut list = LinkedList::from(['A','B','C']);
+        let mut other = LinkedList::new();
+        let e = other.add_last('E');
+        let d = other.add_first('D');
+        other.add_last('F');
+        // Deixa um buraco no pool de other
+        other.remove(e);
+        let e = other.insert_after(d, 'E').unwrap();
+
+        let remap = list.append(&mut other);
+        assert_eq!(list.iter().collect::<String>(), "ABCDEF");
+        assert_eq!(list.iter().rev().collect::<String>(), "FEDCBA");
+        assert_eq!(list.len(), 6);
+        assert_eq!(other.len(), 0);
+        assert_eq!(other.iter().next(), None);
+        assert_eq!(list.get(remap[d]), Some(&'D'));
+        assert_eq!(list.get(remap[e]), Some(&'E'));
+
+        // other continua utilizável
+        other.add_last('x');
+        assert_eq!(other.iter().collect::<String>(), "x");
+        list.append(&mut LinkedList::new());
+        let mut empty = LinkedList::new();
+        empty.append(&mut other);
+        assert_eq!(empty.iter().collect::<String>(), "x");
+
+        let mut tail = list.split_off(remap[d]).unwrap();
+        assert_eq!(list.iter().collect::<String>(), "ABC");
+        assert_eq!(tail.iter().collect::<String>(), "DEF");
+        assert_eq!(list.remove_last(), Some('C'));
+        assert_eq!(tail.remove_first(), Some('D'));
+        assert!(list.split_off(remap[d]).is_none());
+
+        let first = list.index_first();
+        let all = list.split_off(first).unwrap();
+        assert_eq!(list.len(), 0);
+        assert_eq!(all.iter().collect::<String>(), "AB");
+    }
+
+    #[test]
+    pub fn list_splice() {
+        let mut list = LinkedList::new();
+        list.add_last('A');
+        let b = list.add_last('B');
+        let e = list.add_last('E');
+        let mut other = LinkedList::from(['C','D']);
+        let d = other.index_last();
+
+        let remap = list.splice(b, &mut other).unwrap();
+        assert_eq!(list.iter().collect::<String>(), "ABCDE");
+        assert_eq!(list.iter().rev().collect::<String>(), "EDCBA");
+        assert_eq!(other.len(), 0);
+        assert_eq!(list.get(remap[d]), Some(&'D'));
+
+        // Depois do último vira o novo final
+        let mut other = LinkedList::from(['F']);
+        list.splice(e, &mut other).unwrap();
+        assert_eq!(list.iter().collect::<String>(), "ABCDEF");
+        assert_eq!(list.remove_last(), Some('F'));
+
+        // Vazia não muda nada, e nó inexistente não mexe em other
+        list.splice(b, &mut LinkedList::new()).unwrap();
+        assert_eq!(list.len(), 5);
+        let mut other = LinkedList::from(['X']);
+        list.remove(e);
+        assert!(list.splice(e, &mut other).is_none());
+        assert_eq!(other.iter().collect::<String>(), "X");
+        assert_eq!(list.iter().collect::<String>(), "ABCD");
+    }
+
+    #[test]
+    pub fn list_reverse() {
+        let mut list = LinkedList::new();
+        let nodes: Vec<_> = ['A','B','C','D'].into_iter().map(|c| list.add_last(c)).collect();
+        let b = nodes[1];
+        list.reverse();
+        assert_eq!(list.iter().collect::<String>(), "DCBA");
+        assert_eq!(list.iter().rev().collect::<String>(), "ABCD");
+        assert_eq!(list.get(b), Some(&'B'));
+        assert_eq!(list.remove_first(), Some('D'));
+        assert_eq!(list.remove_last(), Some('A'));
+        list.add_first('0');
+        assert_eq!(list.iter().collect::<String>(), "0CB");
+
+        let mut empty: LinkedList<char> = LinkedList::new();
+        empty.reverse();
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    pub fn list_sort() {
+        let mut list = LinkedList::from([5, 3]);
+        let nove = list.add_last(9);
+        list.extend([1, 1, 8, 2, 7, 0]);
+        list.sort();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 1, 2, 3, 5, 7, 8, 9]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![9, 8, 7, 5, 3, 2, 1, 1, 0]);
+        // Os valores não mudaram de lugar no pool
+        assert_eq!(list.get(nove), Some(&9));
+        assert_eq!(list.get(list.index_last()), Some(&9));
+        assert_eq!(list.get(list.index_first()), Some(&0));
+
+        // Estável: mesmo número mantêm a ordem de inserção
+        let mut list: LinkedList<(i32, char)> = [(2,'a'), (1,'b'), (2,'c'), (1,'d'), (0,'e'), (2,'f')].into_iter().collect();
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(list.iter().map(|v| v.1).collect::<String>(), "ebdacf");
+
+        list.sort_by(|a, b| b.1.cmp(&a.1));
+        assert_eq!(list.iter().map(|v| v.1).collect::<String>(), "fedcba");
+
+        for n in 0..20 {
+            let mut list: LinkedList<i32> = (0..n).map(|i| (i * 7919) % 13).collect();
+            let mut expected: Vec<i32> = list.iter().copied().collect();
+            expected.sort();
+            list.sort();
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            assert_eq!(list.len(), n as usize);
+        }
+    }
+
+    #[test]
+    pub fn list_compact_index() {
+        use std::mem::size_of;
+        // Ligações next/prev: 4 bytes com u16, 16 bytes com usize (64 bits)
+        assert_eq!(size_of::<LinkedNode<(), u16>>(), 4);
+        assert_eq!(size_of::<LinkedNode<(), u32>>(), 8);
+        assert_eq!(size_of::<LinkedNode<()>>(), 2 * size_of::<usize>());
+
+        let mut list: LinkedList<char, u16> = LinkedList::new_indexed();
+        let b = list.add_last('B');
+        let a = list.add_first('A');
+        let c = list.insert_after(b, 'C').unwrap();
+        assert_eq!(list.iter().collect::<String>(), "ABC");
+        assert_eq!(list.iter().rev().collect::<String>(), "CBA");
+        assert!(list.move_to_front(c));
+        list.remove(a);
+        assert_eq!(list.iter().collect::<String>(), "CB");
+        assert_eq!(list.cursor_back().peek_next(), None);
+        assert_eq!(list.cursor_front().index(), c);
+
+        list.remove_first();
+        assert_eq!(list.remove_last(), Some('B'));
+        assert_eq!(list.index_first(), u16::MAX);
+        assert_eq!(list.cursor_front().index(), u16::MAX);
+
+        let mut list: LinkedList<i32, u32> = (0..10).rev().collect();
+        list.sort();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        run_queue_tests(&mut LinkedList::<char, u32>::new_indexed());
+    }
+
+    #[test]
+    pub fn list_capacity_overflow() {
+        let mut list: LinkedList<u16, u16> = LinkedList::new_indexed();
+        assert_eq!(list.max_len(), 65535);
+        for i in 0..u16::MAX {
+            list.add_last(i);
+        }
+
+        let err = list.try_add_last(7).unwrap_err();
+        assert_eq!(err.value, 7);
+        assert!(list.try_add_first(8).is_err());
+        assert_eq!(list.len(), 65535);
+
+        // Liberando um nó volta a caber
+        assert_eq!(list.remove_first(), Some(0));
+        let node = list.try_add_first(9).unwrap();
+        assert_eq!(list.get(node), Some(&9));
+        assert_eq!(list.iter().next_back(), Some(&(u16::MAX - 1)));
+
+        let err = list.try_insert_after(node, 10).unwrap_err();
+        assert_eq!(err.value, 10);
+        assert!(list.try_insert_before(node, 11).is_err());
+        assert_eq!(list.try_extend([12, 13]), Err(CapacityOverflowError { value: 12 }));
+
+        // Não mexe em other se as duas juntas não cabem
+        let mut other: LinkedList<u16, u16> = LinkedList::new_indexed();
+        other.add_last(1);
+        other.add_last(2);
+        assert!(list.try_append(&mut other).is_err());
+        assert!(list.try_splice(node, &mut other).is_err());
+        assert_eq!(other.iter().copied().collect::<Vec<u16>>(), vec![1, 2]);
+        assert_eq!(list.len(), 65535);
+
+        // Cabendo exatamente, reaproveitando os espaços vazios
+        list.remove_first();
+        list.remove_first();
+        let remap = list.try_append(&mut other).unwrap();
+        assert_eq!(list.len(), 65535);
+        assert_eq!(list.iter().rev().take(2).copied().collect::<Vec<u16>>(), vec![2, 1]);
+        assert_eq!(remap.len(), 2);
+        assert_eq!(other.len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn list_capacity_overflow_panic() {
+        let mut list: LinkedList<(), u16> = LinkedList::new_indexed();
+        for _ in 0..=u16::MAX {
+            list.add_first(());
+        }
+    }
+
     #[test]
     pub fn linked_list_queue() {
         let mut queue = LinkedList::new();
@@ -265,6 +1482,15 @@ mod test {
         assert_eq!(queue.arr.len(), 0);
     }
 
+    #[test]
+    pub fn linked_list_deque() {
+        let mut list = LinkedList::new();
+        run_deque_tests(&mut list);
+
+        let mut list: LinkedList<char, u16> = LinkedList::new_indexed();
+        run_deque_tests(&mut list);
+    }
+
     #[test]
     pub fn double_linked_stack() {
         let mut stack = LinkedList::new();
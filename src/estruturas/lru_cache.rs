@@ -0,0 +1,322 @@
+use std::{collections::HashMap, fmt, hash::Hash};
+
+use super::LinkedList;
+
+/**
+ * Cache LRU (Least Recently Used) com capacidade fixa
+ *
+ * Funcionamento:
+ * - LinkedList com os pares (chave, valor), o início da lista é o usado mais recentemente e o final o menos
+ * - HashMap da chave para o índice do nó na lista, que não muda ao mover o nó (por isso get/put/pop_lru são O(1))
+ *
+ * Ao acessar uma chave com get/put o nó é movido para o início (move_to_front),
+ * ao inserir com o cache cheio o último nó é removido e passado para o callback on_evict, se houver.
+ *
+ * A chave fica duplicada (no HashMap e no nó), para conseguir remover do HashMap ao descartar o último nó
+ */
+pub struct LruCache<K, V> {
+    map: HashMap<K, usize>,
+    list: LinkedList<(K, V)>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+    on_evict: Option<Box<dyn FnMut(K, V)>>
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /**
+     * Panic se capacity for 0
+     */
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "LruCache: capacidade deve ser maior que 0");
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            list: LinkedList::new(),
+            capacity: capacity,
+            hits: 0,
+            misses: 0,
+            on_evict: None
+        }
+    }
+
+    /**
+     * Cache que chama on_evict(chave, valor) sempre que um par é descartado por falta de espaço
+     * (não é chamado em remove, pop_lru ou clear, nesses casos quem chamou recebe os valores)
+     */
+    pub fn with_on_evict<F: FnMut(K, V) + 'static>(capacity: usize, on_evict: F) -> LruCache<K, V> {
+        let mut ret = LruCache::new(capacity);
+        ret.on_evict = Some(Box::new(on_evict));
+        return ret;
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /**
+     * Muda a capacidade, se diminuir descarta os menos usados (chamando on_evict)
+     */
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0, "LruCache: capacidade deve ser maior que 0");
+        self.capacity = capacity;
+        while self.len() > self.capacity {
+            self.evict();
+        }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /**
+     * Obtêm o valor e marca como usado mais recentemente. Conta como hit ou miss nas estatísticas
+     */
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        return self.get_mut(key).map(|value| &*value);
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.map.get(key) {
+            Some(&node) => {
+                self.hits += 1;
+                self.list.move_to_front(node);
+                return self.list.get_mut(node).map(|(_, value)| value);
+            },
+            None => {
+                self.misses += 1;
+                return None;
+            }
+        }
+    }
+
+    /**
+     * Obtêm o valor sem mudar a ordem de uso nem as estatísticas
+     */
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let node = *self.map.get(key)?;
+        return self.list.get(node).map(|(_, value)| value);
+    }
+
+    /**
+     * Insere ou atualiza o valor, que passa a ser o usado mais recentemente
+     *
+     * Retorna o valor antigo se a chave já existia. Se o cache estava cheio descarta o menos usado (chamando on_evict)
+     */
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&node) = self.map.get(&key) {
+            self.list.move_to_front(node);
+            let (_, old) = self.list.get_mut(node).unwrap();
+            return Some(std::mem::replace(old, value));
+        }
+
+        if self.len() >= self.capacity {
+            self.evict();
+        }
+        let node = self.list.add_first((key.clone(), value));
+        self.map.insert(key, node);
+        return None;
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.map.remove(key)?;
+        let (_, value) = self.list.remove(node).unwrap();
+        return Some(value);
+    }
+
+    /**
+     * Remove e retorna o par usado menos recentemente (não chama on_evict)
+     */
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.list.remove_last()?;
+        self.map.remove(&key);
+        return Some((key, value));
+    }
+
+    /**
+     * Par usado menos recentemente, sem remover
+     */
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        let (key, value) = self.list.get(self.list.index_last())?;
+        return Some((key, value));
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.list.clear();
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /**
+     * hits / (hits + misses), 0.0 se nunca foi feito get
+     */
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { return 0.0; }
+        return self.hits as f64 / total as f64;
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /**
+     * Atravessa do usado mais recentemente ao menos, sem mudar a ordem
+     */
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + '_ {
+        self.list.iter().map(|(key, value)| (key, value))
+    }
+
+    fn evict(&mut self) {
+        let evicted = self.pop_lru();
+        if let (Some((key, value)), Some(on_evict)) = (evicted, self.on_evict.as_mut()) {
+            on_evict(key, value);
+        }
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.list.iter().map(|(key, value)| (key, value))).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::LruCache;
+
+    #[test]
+    pub fn lru_methods() {
+        let mut cache = LruCache::new(3);
+        assert_eq!(cache.put('A', 1), None);
+        assert_eq!(cache.put('B', 2), None);
+        assert_eq!(cache.put('C', 3), None);
+        assert_eq!(cache.len(), 3);
+
+        // A passa a ser o mais recente, B é o próximo a sair
+        assert_eq!(cache.get(&'A'), Some(&1));
+        assert_eq!(cache.peek_lru(), Some((&'B', &2)));
+        assert_eq!(cache.put('D', 4), None);
+        assert!(!cache.contains(&'B'));
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<String>(), "DAC");
+
+        // Atualizar não aumenta o tamanho
+        assert_eq!(cache.put('C', 30), Some(3));
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<String>(), "CDA");
+
+        // peek não muda a ordem
+        assert_eq!(cache.peek(&'A'), Some(&1));
+        assert_eq!(cache.pop_lru(), Some(('A', 1)));
+        *cache.get_mut(&'D').unwrap() += 10;
+        assert_eq!(cache.remove(&'D'), Some(14));
+        assert_eq!(cache.remove(&'D'), None);
+        assert_eq!(format!("{:?}", cache), "{'C': 30}");
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.pop_lru(), None);
+        assert_eq!(cache.peek_lru(), None);
+    }
+
+    #[test]
+    pub fn lru_evict_and_stats() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let evicted_clone = evicted.clone();
+        let mut cache = LruCache::with_on_evict(2, move |k, v| evicted_clone.borrow_mut().push((k, v)));
+
+        cache.put("um", 1);
+        cache.put("dois", 2);
+        assert_eq!(cache.get(&"um"), Some(&1));
+        assert_eq!(cache.get(&"tres"), None);
+        cache.put("tres", 3);
+        assert_eq!(*evicted.borrow(), vec![("dois", 2)]);
+
+        // pop_lru e remove não chamam o callback
+        cache.pop_lru();
+        cache.remove(&"tres");
+        assert_eq!(evicted.borrow().len(), 1);
+
+        cache.put("a", 10);
+        cache.put("b", 20);
+        cache.put("c", 30);
+        cache.set_capacity(1);
+        assert_eq!(*evicted.borrow(), vec![("dois", 2), ("a", 10), ("b", 20)]);
+        assert_eq!(cache.len(), 1);
+
+        assert_eq!(cache.get(&"c"), Some(&30));
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+        assert!((cache.hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+        cache.reset_stats();
+        assert_eq!(cache.hit_rate(), 0.0);
+    }
+
+    #[test]
+    pub fn lru_naive_model() {
+        // Modelo ingênuo: Vec com o mais recente no início, busca linear
+        let capacity = 8;
+        let mut model: Vec<(u32, u32)> = Vec::new();
+        let mut cache = LruCache::new(capacity);
+        let mut model_hits = 0;
+        let mut model_misses = 0;
+
+        // Semente fixa para o teste ser reproduzível
+        let mut rng = StdRng::seed_from_u64(12345);
+
+        for i in 0..5000 {
+            let key = rng.random_range(0..16);
+            match rng.random_range(0..4) {
+                0 | 1 => {
+                    let expected = model.iter().position(|(k, _)| *k == key).map(|pos| {
+                        let par = model.remove(pos);
+                        model.insert(0, par);
+                        par.1
+                    });
+                    if expected.is_some() { model_hits += 1; } else { model_misses += 1; }
+                    assert_eq!(cache.get(&key).copied(), expected);
+                },
+                2 => {
+                    let old = model.iter().position(|(k, _)| *k == key).map(|pos| model.remove(pos).1);
+                    if old.is_none() && model.len() >= capacity {
+                        model.pop();
+                    }
+                    model.insert(0, (key, i));
+                    assert_eq!(cache.put(key, i), old);
+                },
+                _ => {
+                    if rng.random_bool(0.5) {
+                        let expected = model.iter().position(|(k, _)| *k == key).map(|pos| model.remove(pos).1);
+                        assert_eq!(cache.remove(&key), expected);
+                    } else {
+                        assert_eq!(cache.pop_lru(), model.pop());
+                    }
+                }
+            }
+
+            assert_eq!(cache.len(), model.len());
+            assert_eq!(cache.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), model);
+        }
+        assert_eq!(cache.hits(), model_hits);
+        assert_eq!(cache.misses(), model_misses);
+    }
+}
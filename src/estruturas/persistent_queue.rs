@@ -0,0 +1,219 @@
+use std::{fmt, rc::Rc};
+
+use super::{LinkedStack, Queue, Stack};
+
+/**
+ * Fila persistente (imutável, com compartilhamento de estrutura), a versão em fila do LinkedStack
+ *
+ * Banker's Queue (Okasaki, Purely Functional Data Structures) com duas LinkedStack:
+ * - front: início da fila, o topo é o próximo a sair no dequeue()
+ * - rear: final da fila, o topo é o último que entrou com enqueue()
+ *
+ * Sempre que rear fica maior que front, a fila é 'rotacionada': front = front ++ reverse(rear).
+ * Como as pilhas compartilham os nós via Rc, clone() é O(1) (só duas referências) e
+ * versões antigas continuam válidas depois de enqueue/dequeue em uma cópia.
+ *
+ * Os valores ficam em Rc, assim a rotação e as versões compartilham os valores sem clonar T
+ * (T::clone só acontece no dequeue, se outra versão ainda tem o mesmo valor)
+ *
+ * Limitação (fila estrita, sem avaliação preguiçosa): a rotação cria O(n) nós novos.
+ * Isso é O(1) amortizado somente se cada versão for usada uma vez, fazer dequeue várias vezes
+ * na mesma versão antiga logo antes de uma rotação repete a rotação (O(n) cada vez, mas só copiando os Rc)
+ */
+#[derive(Clone)]
+pub struct PersistentQueue<T> {
+    front: LinkedStack<Rc<T>>,
+    rear: LinkedStack<Rc<T>>,
+    // Último valor inserido (o mesmo Rc que está em rear), para tail() ser O(1) mesmo quando rear está vazio depois de uma rotação
+    last: Option<Rc<T>>
+}
+
+impl<T: Clone> PersistentQueue<T> {
+    pub fn new() -> PersistentQueue<T> {
+        PersistentQueue { front: LinkedStack::new(), rear: LinkedStack::new(), last: None }
+    }
+
+    pub fn from<const N: usize>(values: [T; N]) -> PersistentQueue<T> {
+        let mut ret = PersistentQueue::new();
+        ret.enqueue_values(values);
+        return ret;
+    }
+
+    pub fn len(&self) -> usize {
+        (self.front.len() + self.rear.len()) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.front.len() == 0
+    }
+
+    /**
+     * Nova versão com o valor no final, esta continua igual
+     */
+    pub fn enqueued(&self, value: T) -> PersistentQueue<T> {
+        let mut ret = self.clone();
+        ret.enqueue(value);
+        return ret;
+    }
+
+    /**
+     * Nova versão sem o primeiro valor (e o valor removido), esta continua igual
+     */
+    pub fn dequeued(&self) -> Option<(T, PersistentQueue<T>)> {
+        let mut ret = self.clone();
+        let value = ret.dequeue()?;
+        return Some((value, ret));
+    }
+
+    /**
+     * Atravessa do início ao final da fila
+     * (rear precisa ser invertido, então seus valores são coletados em um Vec antes)
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let rear: Vec<&T> = self.rear.iter().map(Rc::as_ref).collect();
+        self.front.iter().map(Rc::as_ref).chain(rear.into_iter().rev())
+    }
+
+    /**
+     * Mantêm rear.len() <= front.len(), assim front só fica vazio se a fila estiver vazia
+     */
+    fn rotate(&mut self) {
+        if self.rear.len() <= self.front.len() { return; }
+
+        // 1 - reverse(rear): o primeiro a sair de rear é o último inserido, que fica no fundo
+        let mut new_front = LinkedStack::new();
+        while let Some(value) = self.rear.pop() {
+            new_front.push(value);
+        }
+
+        // 2 - Os valores de front ficam por cima, na mesma ordem (nós novos, mas os valores são os mesmos Rc)
+        let front: Vec<&Rc<T>> = self.front.iter().collect();
+        for value in front.into_iter().rev() {
+            new_front.push(Rc::clone(value));
+        }
+        self.front = new_front;
+    }
+}
+
+impl<T: Clone> Queue<T> for PersistentQueue<T> {
+    fn enqueue(&mut self, value: T) {
+        let value = Rc::new(value);
+        self.last = Some(Rc::clone(&value));
+        self.rear.push(value);
+        self.rotate();
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        let ret = self.front.pop()?;
+        if self.front.len() == 0 {
+            // Antes do try_unwrap, senão o último valor nunca poderia ser movido
+            self.last = None;
+        }
+        self.rotate();
+        // Só clona se outra versão da fila ainda tem este valor
+        return Some(Rc::try_unwrap(ret).unwrap_or_else(|shared| shared.as_ref().clone()));
+    }
+
+    fn head(&self) -> Option<&T> {
+        self.front.peek().map(Rc::as_ref)
+    }
+
+    fn tail(&self) -> Option<&T> {
+        self.last.as_deref()
+    }
+
+    fn len(&self) -> usize {
+        PersistentQueue::len(self)
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for PersistentQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::estruturas::{run_queue_tests, Queue};
+
+    use super::PersistentQueue;
+
+    #[test]
+    pub fn persistent_queue() {
+        let mut queue = PersistentQueue::new();
+        run_queue_tests(&mut queue);
+    }
+
+    #[test]
+    pub fn persistent_queue_versions() {
+        let v1 = PersistentQueue::from(['A','B','C']);
+        let v2 = v1.enqueued('D');
+        let (a, v3) = v2.dequeued().unwrap();
+        let v4 = v3.enqueued('X');
+
+        assert_eq!(a, 'A');
+        assert_eq!(v1.iter().collect::<String>(), "ABC");
+        assert_eq!(v2.iter().collect::<String>(), "ABCD");
+        assert_eq!(v3.iter().collect::<String>(), "BCD");
+        assert_eq!(v4.iter().collect::<String>(), "BCDX");
+        assert_eq!(v1.tail(), Some(&'C'));
+        assert_eq!(v2.tail(), Some(&'D'));
+        assert_eq!(v4.head(), Some(&'B'));
+        assert_eq!(v4.len(), 4);
+
+        // Esvaziar uma cópia não afeta a original
+        let mut copia = v4.clone();
+        while copia.dequeue().is_some() {}
+        assert!(copia.is_empty());
+        assert_eq!(copia.tail(), None);
+        assert_eq!(format!("{:?}", v4), "['B', 'C', 'D', 'X']");
+
+        // Enfileirar depois de várias rotações continua em ordem
+        let mut queue = PersistentQueue::new();
+        let mut versions = Vec::new();
+        for i in 0..100 {
+            queue.enqueue(i);
+            if i % 3 == 0 { queue.dequeue(); }
+            versions.push(queue.clone());
+        }
+        for (i, version) in versions.iter().enumerate() {
+            let removed = i / 3 + 1;
+            assert_eq!(version.iter().copied().collect::<Vec<_>>(), (removed..=i).collect::<Vec<_>>());
+            assert_eq!(version.tail(), if removed <= i { Some(&i) } else { None });
+        }
+    }
+
+    #[test]
+    pub fn persistent_queue_clones() {
+        use std::{cell::Cell, rc::Rc};
+
+        struct Contador(Rc<Cell<i32>>);
+        impl Clone for Contador {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Contador(self.0.clone())
+            }
+        }
+
+        // Enfileirar e rotacionar não clonam os valores
+        let clones = Rc::new(Cell::new(0));
+        let mut queue = PersistentQueue::new();
+        for _ in 0..10 {
+            queue.enqueue(Contador(clones.clone()));
+        }
+        let antiga = queue.clone();
+        for _ in 0..3 {
+            let mut copia = antiga.clone();
+            copia.dequeue();
+            copia.dequeue();
+        }
+        assert_eq!(clones.get(), 6);
+
+        // Sem outra versão, o dequeue move o valor
+        drop(antiga);
+        clones.set(0);
+        while queue.dequeue().is_some() {}
+        assert_eq!(clones.get(), 0);
+    }
+}
@@ -0,0 +1,507 @@
+use std::{cmp::Ordering, fmt};
+
+use super::{PoolHandle, Queue, VecPool};
+
+/**
+ * Fila de prioridade em um heap binário (min-heap): sai primeiro o de MENOR prioridade
+ * (para sair o de maior, usar std::cmp::Reverse(prioridade))
+ *
+ * Funcionamento:
+ * - O heap é uma árvore binária completa guardada em um Vec: filhos de i estão em 2i+1 e 2i+2, pai em (i-1)/2
+ * - Cada pai tem prioridade menor ou igual aos filhos, então o menor sempre está na posição 0
+ * - enqueue: coloca no final e 'sobe' trocando com o pai enquanto for menor (sift up), O(log n)
+ * - dequeue: troca o primeiro com o último, remove o último e 'desce' o novo primeiro (sift down), O(log n)
+ *
+ * Implementa Queue<(T, P)>, ou seja, enqueue((valor, prioridade)).
+ * Em caso de empate sai primeiro quem entrou primeiro (estável), por isso cada item guarda um número de sequência
+ */
+pub struct PriorityQueue<T, P> {
+    heap: Vec<HeapEntry<(T, P)>>,
+    sequence: u64
+}
+
+struct HeapEntry<V> {
+    item: V,
+    sequence: u64
+}
+
+/**
+ * Ordem do heap: prioridade e depois sequência (quem entrou antes)
+ */
+fn heap_order<P: Ord>(a: &P, a_seq: u64, b: &P, b_seq: u64) -> Ordering {
+    a.cmp(b).then(a_seq.cmp(&b_seq))
+}
+
+impl<T, P: Ord> PriorityQueue<T, P> {
+    pub fn new() -> PriorityQueue<T, P> {
+        PriorityQueue { heap: Vec::new(), sequence: 0 }
+    }
+
+    pub fn with_capacity(capacity: usize) -> PriorityQueue<T, P> {
+        PriorityQueue { heap: Vec::with_capacity(capacity), sequence: 0 }
+    }
+
+    pub fn push(&mut self, value: T, priority: P) {
+        self.heap.push(HeapEntry { item: (value, priority), sequence: self.sequence });
+        self.sequence += 1;
+        let last = self.heap.len() - 1;
+        self.sift_up(last);
+    }
+
+    /**
+     * Remove o de menor prioridade
+     */
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        if self.heap.is_empty() { return None; }
+
+        let last = self.heap.len() - 1;
+        self.heap.swap(0, last);
+        let ret = self.heap.pop().unwrap();
+        self.sift_down(0);
+        return Some(ret.item);
+    }
+
+    /**
+     * 'Empresta' o de menor prioridade (o próximo que sairia no pop)
+     */
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        let (value, priority) = &self.heap.first()?.item;
+        return Some((value, priority));
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /**
+     * Atravessa na ordem do heap (NÃO está ordenado por prioridade)
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &P)> + '_ {
+        self.heap.iter().map(|entry| (&entry.item.0, &entry.item.1))
+    }
+
+    /**
+     * Consome a fila retornando os valores em ordem de prioridade (heap sort), O(n log n)
+     */
+    pub fn into_sorted_vec(mut self) -> Vec<(T, P)> {
+        let mut ret = Vec::with_capacity(self.len());
+        while let Some(item) = self.pop() {
+            ret.push(item);
+        }
+        return ret;
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.heap[a], &self.heap[b]);
+        heap_order(&a.item.1, a.sequence, &b.item.1, b.sequence) == Ordering::Less
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) { break; }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut menor = i;
+            for filho in [2 * i + 1, 2 * i + 2] {
+                if filho < self.heap.len() && self.less(filho, menor) {
+                    menor = filho;
+                }
+            }
+            if menor == i { break; }
+            self.heap.swap(i, menor);
+            i = menor;
+        }
+    }
+}
+
+impl<T, P: Ord> Queue<(T, P)> for PriorityQueue<T, P> {
+    fn enqueue(&mut self, value: (T, P)) {
+        self.push(value.0, value.1);
+    }
+
+    fn dequeue(&mut self) -> Option<(T, P)> {
+        self.pop()
+    }
+
+    fn head(&self) -> Option<&(T, P)> {
+        Some(&self.heap.first()?.item)
+    }
+
+    /**
+     * O último que sairia (maior prioridade). Está em alguma folha do heap, então é O(n)
+     */
+    fn tail(&self) -> Option<&(T, P)> {
+        let entry = self.heap.iter().max_by(|a, b| heap_order(&a.item.1, a.sequence, &b.item.1, b.sequence))?;
+        return Some(&entry.item);
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+impl<T, P: Ord> FromIterator<(T, P)> for PriorityQueue<T, P> {
+    fn from_iter<I: IntoIterator<Item = (T, P)>>(iter: I) -> Self {
+        let mut ret = PriorityQueue::new();
+        for (value, priority) in iter {
+            ret.push(value, priority);
+        }
+        return ret;
+    }
+}
+
+impl<T: fmt::Debug, P: fmt::Debug + Ord> fmt::Debug for PriorityQueue<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/**
+ * Fila de prioridade indexada: cada item inserido recebe um PoolHandle, que permite
+ * mudar a prioridade (decrease_key) ou remover o item depois, em O(log n)
+ *
+ * Funcionamento:
+ * - Os itens ficam em um VecPool, junto da posição atual deles no heap
+ * - O heap guarda somente os índices no pool, ao trocar dois elementos de lugar a posição no pool é atualizada
+ * - Com o handle se encontra a posição no heap em O(1), e então basta fazer sift up/down a partir dela
+ *
+ * Útil para Dijkstra/Prim: ao achar um caminho melhor para um vértice já na fila, diminui a prioridade dele
+ * ao invés de inserir de novo
+ */
+pub struct IndexedPriorityQueue<T, P> {
+    pool: VecPool<IndexedEntry<T, P>>,
+    heap: Vec<usize>,
+    sequence: u64
+}
+
+struct IndexedEntry<T, P> {
+    item: (T, P),
+    sequence: u64,
+    heap_index: usize
+}
+
+impl<T, P: Ord> IndexedPriorityQueue<T, P> {
+    pub fn new() -> IndexedPriorityQueue<T, P> {
+        IndexedPriorityQueue { pool: VecPool::new(), heap: Vec::new(), sequence: 0 }
+    }
+
+    /**
+     * Insere e retorna o handle do item, válido até ele sair da fila
+     */
+    pub fn push(&mut self, value: T, priority: P) -> PoolHandle {
+        let heap_index = self.heap.len();
+        let handle = self.pool.alloc_node(IndexedEntry { item: (value, priority), sequence: self.sequence, heap_index: heap_index });
+        self.sequence += 1;
+        self.heap.push(handle.index());
+        self.sift_up(heap_index);
+        return handle;
+    }
+
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        let first = *self.heap.first()?;
+        return self.remove_at(first);
+    }
+
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        let (value, priority) = &self.pool[*self.heap.first()?].item;
+        return Some((value, priority));
+    }
+
+    pub fn contains(&self, handle: PoolHandle) -> bool {
+        self.pool.contains(handle)
+    }
+
+    pub fn get(&self, handle: PoolHandle) -> Option<(&T, &P)> {
+        let (value, priority) = &self.pool.get_node(handle)?.item;
+        return Some((value, priority));
+    }
+
+    /**
+     * Diminui a prioridade do item. Retorna false (e não altera) se o handle não está mais na fila
+     * ou se a nova prioridade é maior que a atual (nesse caso usar change_priority)
+     */
+    pub fn decrease_key(&mut self, handle: PoolHandle, priority: P) -> bool {
+        match self.pool.get_node(handle) {
+            Some(entry) if priority <= entry.item.1 => {},
+            _ => { return false; }
+        }
+        return self.change_priority(handle, priority);
+    }
+
+    /**
+     * Muda a prioridade do item para qualquer valor (maior ou menor), reposicionando no heap
+     */
+    pub fn change_priority(&mut self, handle: PoolHandle, priority: P) -> bool {
+        let heap_index = match self.pool.get_mut_node(handle) {
+            Some(entry) => {
+                entry.item.1 = priority;
+                entry.heap_index
+            },
+            None => { return false; }
+        };
+
+        self.sift_up(heap_index);
+        let heap_index = self.pool[handle].heap_index;
+        self.sift_down(heap_index);
+        return true;
+    }
+
+    /**
+     * Remove o item de qualquer posição da fila
+     */
+    pub fn remove(&mut self, handle: PoolHandle) -> Option<(T, P)> {
+        if !self.pool.contains(handle) { return None; }
+        return self.remove_at(handle.index());
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.pool.clear();
+        self.heap.clear();
+    }
+
+    /**
+     * Remove o item (índice no pool), colocando o último do heap no lugar dele
+     */
+    fn remove_at(&mut self, node: usize) -> Option<(T, P)> {
+        let heap_index = self.pool[node].heap_index;
+        let last = self.heap.len() - 1;
+        self.swap(heap_index, last);
+        self.heap.pop();
+        let ret = self.pool.free_node(node)?;
+
+        if heap_index < self.heap.len() {
+            self.sift_up(heap_index);
+            let moved = self.heap[heap_index];
+            let heap_index = self.pool[moved].heap_index;
+            self.sift_down(heap_index);
+        }
+        return Some(ret.item);
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.pool[self.heap[a]], &self.pool[self.heap[b]]);
+        heap_order(&a.item.1, a.sequence, &b.item.1, b.sequence) == Ordering::Less
+    }
+
+    /**
+     * Troca duas posições do heap, atualizando a posição guardada no pool
+     */
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        let (node_a, node_b) = (self.heap[a], self.heap[b]);
+        self.pool[node_a].heap_index = a;
+        self.pool[node_b].heap_index = b;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) { break; }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut menor = i;
+            for filho in [2 * i + 1, 2 * i + 2] {
+                if filho < self.heap.len() && self.less(filho, menor) {
+                    menor = filho;
+                }
+            }
+            if menor == i { break; }
+            self.swap(i, menor);
+            i = menor;
+        }
+    }
+}
+
+impl<T, P: Ord> Queue<(T, P)> for IndexedPriorityQueue<T, P> {
+    fn enqueue(&mut self, value: (T, P)) {
+        self.push(value.0, value.1);
+    }
+
+    fn dequeue(&mut self) -> Option<(T, P)> {
+        self.pop()
+    }
+
+    fn head(&self) -> Option<&(T, P)> {
+        Some(&self.pool[*self.heap.first()?].item)
+    }
+
+    /**
+     * O último que sairia (maior prioridade), O(n)
+     */
+    fn tail(&self) -> Option<&(T, P)> {
+        let entry = self.pool.values().max_by(|a, b| heap_order(&a.item.1, a.sequence, &b.item.1, b.sequence))?;
+        return Some(&entry.item);
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Reverse;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::estruturas::Queue;
+
+    use super::{IndexedPriorityQueue, PriorityQueue};
+
+    /**
+     * Mesmo roteiro do run_queue_tests, mas com prioridades crescentes (sai na ordem de inserção)
+     */
+    fn run_priority_queue_tests<Q: Queue<(char, i32)>>(queue: &mut Q) {
+        for iter in 0..3 {
+            assert_eq!(queue.head(), None);
+            assert_eq!(queue.tail(), None);
+
+            queue.enqueue_values([('C', 3), ('A', 1), ('E', 5), ('B', 2), ('D', 4)]);
+            assert_eq!(queue.len(), 5);
+            assert_eq!(queue.head(), Some(&('A', 1)));
+            assert_eq!(queue.tail(), Some(&('E', 5)));
+
+            assert_eq!(queue.dequeue(), Some(('A', 1)));
+            assert_eq!(queue.dequeue(), Some(('B', 2)));
+            queue.enqueue(('F', 6));
+            queue.enqueue(('0', 0));
+            assert_eq!(queue.dequeue(), Some(('0', 0)));
+            assert_eq!(queue.dequeue(), Some(('C', 3)));
+            assert_eq!(queue.dequeue(), Some(('D', 4)));
+            assert_eq!(queue.dequeue(), Some(('E', 5)));
+            assert_eq!(queue.dequeue(), Some(('F', 6)));
+            assert_eq!(queue.dequeue(), None);
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    pub fn priority_queue() {
+        run_priority_queue_tests(&mut PriorityQueue::new());
+
+        // Empates saem na ordem de inserção
+        let mut queue = PriorityQueue::new();
+        queue.push('a', 2);
+        queue.push('b', 1);
+        queue.push('c', 2);
+        queue.push('d', 1);
+        queue.push('e', 2);
+        assert_eq!(queue.peek(), Some((&'b', &1)));
+        assert_eq!(queue.into_sorted_vec().into_iter().map(|(v, _)| v).collect::<String>(), "bdace");
+
+        // Max-heap com Reverse
+        let queue: PriorityQueue<i32, Reverse<i32>> = [5, 1, 8, 3, 9, 2].into_iter().map(|v| (v, Reverse(v))).collect();
+        assert_eq!(queue.into_sorted_vec().into_iter().map(|(v, _)| v).collect::<Vec<_>>(), vec![9, 8, 5, 3, 2, 1]);
+    }
+
+    #[test]
+    pub fn priority_queue_heap_sort() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut values = Vec::new();
+        let mut queue = PriorityQueue::with_capacity(1000);
+        for i in 0..1000 {
+            let priority: u32 = rng.random_range(0..100);
+            values.push((i, priority));
+            queue.push(i, priority);
+        }
+        assert_eq!(queue.len(), 1000);
+
+        // sort_by_key é estável, igual à fila
+        values.sort_by_key(|(_, priority)| *priority);
+        assert_eq!(queue.into_sorted_vec(), values);
+    }
+
+    #[test]
+    pub fn indexed_priority_queue() {
+        run_priority_queue_tests(&mut IndexedPriorityQueue::new());
+
+        let mut queue = IndexedPriorityQueue::new();
+        let a = queue.push('A', 10);
+        let b = queue.push('B', 20);
+        let c = queue.push('C', 30);
+        let d = queue.push('D', 40);
+        assert_eq!(queue.peek(), Some((&'A', &10)));
+
+        assert!(queue.decrease_key(d, 5));
+        assert_eq!(queue.peek(), Some((&'D', &5)));
+        // Não aumenta com decrease_key
+        assert!(!queue.decrease_key(b, 50));
+        assert_eq!(queue.get(b), Some((&'B', &20)));
+        assert!(queue.change_priority(d, 25));
+
+        assert_eq!(queue.remove(b), Some(('B', 20)));
+        assert_eq!(queue.remove(b), None);
+        assert!(!queue.contains(b));
+        assert!(!queue.decrease_key(b, 0));
+
+        assert_eq!(queue.pop(), Some(('A', 10)));
+        assert_eq!(queue.pop(), Some(('D', 25)));
+        assert!(queue.contains(c));
+        assert_eq!(queue.pop(), Some(('C', 30)));
+        assert_eq!(queue.pop(), None);
+        assert!(!queue.contains(a));
+    }
+
+    #[test]
+    pub fn indexed_priority_queue_model() {
+        // Compara com a ordenação de um Vec depois de várias mudanças de prioridade e remoções
+        let mut queue = IndexedPriorityQueue::new();
+        let mut rng = StdRng::seed_from_u64(99);
+
+        let mut handles = Vec::new();
+        let mut model: Vec<(usize, u32)> = Vec::new();
+        for i in 0..300 {
+            let priority = rng.random_range(0..1000);
+            handles.push(queue.push(i, priority));
+            model.push((i, priority));
+        }
+        for _ in 0..500 {
+            let i = rng.random_range(0..300);
+            let priority = rng.random_range(0..1000);
+            if rng.random_range(0..5) == 0 {
+                assert_eq!(queue.remove(handles[i]).is_some(), model[i].1 != u32::MAX);
+                model[i].1 = u32::MAX;
+            } else if model[i].1 != u32::MAX {
+                assert!(queue.change_priority(handles[i], priority));
+                model[i].1 = priority;
+            }
+        }
+
+        // Com mudanças de prioridade a ordem de empate é a da inserção original (sequência)
+        model.retain(|(_, priority)| *priority != u32::MAX);
+        model.sort_by_key(|(_, priority)| *priority);
+        assert_eq!(queue.len(), model.len());
+        let mut ret = Vec::new();
+        while let Some(item) = queue.pop() {
+            ret.push(item);
+        }
+        assert_eq!(ret, model);
+    }
+}
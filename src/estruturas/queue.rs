@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{error::Error, fmt, iter::{Chain, Rev}, marker::PhantomData, slice, vec};
 
 pub trait Queue<T> {
     /**
@@ -16,6 +16,16 @@ pub trait Queue<T> {
         }
     }
 
+    /**
+     * Insere no final sem panic em uma fila limitada cheia (filas sem limite sempre inserem):
+     * - Ok(None) se inseriu, Ok(Some(removido)) se precisou descartar o mais antigo
+     * - Err com o valor se a fila recusou
+     */
+    fn try_enqueue(&mut self, value: T) -> Result<Option<T>, QueueFullError<T>> {
+        self.enqueue(value);
+        return Ok(None);
+    }
+
     /**
      * Remove um elemento do início da fila
      */
@@ -30,18 +40,75 @@ pub trait Queue<T> {
      * 'Empresta' o valor do final da fila (Do lado que insere, o último que foi inserido com enqueue())
      */
     fn tail(&self) -> Option<&T>;
+
+    /**
+     * Quantidade de elementos na fila
+     */
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * Limite de elementos que a fila aceita, None se não tem limite
+     */
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
 }
 
+/**
+ * O que fazer ao inserir em uma fila limitada que já está cheia
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    // Não insere, try_enqueue retorna erro com o valor
+    Reject,
+    // Remove o mais antigo (início da fila) para dar lugar ao novo, como um histórico de tamanho fixo
+    EvictOldest
+}
+
+/**
+ * Erro ao inserir em uma fila limitada cheia (com OverflowPolicy::Reject), devolve o valor
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueFullError<T> {
+    pub value: T
+}
+
+impl<T> fmt::Display for QueueFullError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a fila está cheia")
+    }
+}
+
+impl<T: fmt::Debug> Error for QueueFullError<T> {}
+
 pub struct DoubleStackQueue<T> {
     entrada: Vec<T>,
-    saida: Vec<T>
+    saida: Vec<T>,
+    // Some((capacidade, política)) se a fila é limitada
+    limit: Option<(usize, OverflowPolicy)>
 }
 
 impl<T> DoubleStackQueue<T> {
     pub fn new() -> DoubleStackQueue<T> {
         DoubleStackQueue {
             entrada: Vec::new(),
-            saida: Vec::new()
+            saida: Vec::new(),
+            limit: None
+        }
+    }
+
+    /**
+     * Fila com no máximo capacity elementos, ao encher segue a política informada
+     */
+    pub fn bounded(capacity: usize, policy: OverflowPolicy) -> DoubleStackQueue<T> {
+        DoubleStackQueue {
+            entrada: Vec::with_capacity(capacity),
+            saida: Vec::new(),
+            limit: Some((capacity, policy))
         }
     }
 
@@ -55,14 +122,68 @@ impl<T> DoubleStackQueue<T> {
         DoubleStackQueueIterator { queue: &self, index: 0 }
     }
 
-    fn len(&self) -> i32 {
-        return (self.entrada.len() + self.saida.len()) as i32;
+    /**
+     * Atravessa do início ao final com referências mutáveis
+     */
+    pub fn iter_mut(&mut self) -> DoubleStackQueueIterMut<'_, T> {
+        DoubleStackQueueIterMut { inner: self.saida.iter_mut().rev().chain(self.entrada.iter_mut()) }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entrada.len() + self.saida.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        return self.limit.map(|(capacity, _)| capacity);
+    }
+
+    pub fn clear(&mut self) {
+        self.entrada.clear();
+        self.saida.clear();
+    }
+
+    /**
+     * Insere no final respeitando o limite da fila:
+     * - Sem limite ou com espaço: Ok(None)
+     * - Cheia com EvictOldest: remove o mais antigo e retorna Ok(Some(removido))
+     * - Cheia com Reject: não insere e retorna Err com o valor
+     */
+    pub fn try_enqueue(&mut self, value: T) -> Result<Option<T>, QueueFullError<T>> {
+        let mut evicted = None;
+        if let Some((capacity, policy)) = self.limit && self.len() >= capacity {
+            match policy {
+                OverflowPolicy::Reject => { return Err(QueueFullError { value: value }); },
+                OverflowPolicy::EvictOldest => {
+                    // Com capacidade 0 o próprio valor é descartado
+                    if capacity == 0 { return Ok(Some(value)); }
+                    evicted = self.dequeue();
+                },
+            }
+        }
+
+        self.entrada.push(value);
+        return Ok(evicted);
     }
 }
 
 impl<T> Queue<T> for DoubleStackQueue<T> {
+    /**
+     * Panic se a fila é limitada com OverflowPolicy::Reject e está cheia, ver try_enqueue
+     */
     fn enqueue(&mut self, value: T) {
-        self.entrada.push(value);
+        if DoubleStackQueue::try_enqueue(self, value).is_err() {
+            // Só tem erro com limite e OverflowPolicy::Reject
+            let (capacity, _) = self.limit.unwrap();
+            panic!("DoubleStackQueue: fila cheia (capacidade {})", capacity);
+        }
+    }
+
+    fn try_enqueue(&mut self, value: T) -> Result<Option<T>, QueueFullError<T>> {
+        DoubleStackQueue::try_enqueue(self, value)
     }
 
     fn tail(&self) -> Option<&T> {
@@ -90,6 +211,14 @@ impl<T> Queue<T> for DoubleStackQueue<T> {
         }
         return self.saida.pop();
     }
+
+    fn len(&self) -> usize {
+        DoubleStackQueue::len(self)
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        DoubleStackQueue::capacity(self)
+    }
 }
 
 pub struct DoubleStackQueueIterator<'a,T> {
@@ -120,6 +249,63 @@ impl<'a,T> Iterator for DoubleStackQueueIterator<'a,T> {
     }
 }
 
+pub struct DoubleStackQueueIterMut<'a,T> {
+    inner: Chain<Rev<slice::IterMut<'a, T>>, slice::IterMut<'a, T>>
+}
+
+impl<'a,T> Iterator for DoubleStackQueueIterMut<'a,T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/**
+ * Iterador que consome a fila, do início ao final
+ */
+pub struct DoubleStackQueueIntoIter<T> {
+    inner: Chain<Rev<vec::IntoIter<T>>, vec::IntoIter<T>>
+}
+
+impl<T> Iterator for DoubleStackQueueIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> IntoIterator for DoubleStackQueue<T> {
+    type Item = T;
+    type IntoIter = DoubleStackQueueIntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        DoubleStackQueueIntoIter { inner: self.saida.into_iter().rev().chain(self.entrada) }
+    }
+}
+
+impl<'a,T> IntoIterator for &'a DoubleStackQueue<T> {
+    type Item = &'a T;
+    type IntoIter = DoubleStackQueueIterator<'a,T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a,T> IntoIterator for &'a mut DoubleStackQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = DoubleStackQueueIterMut<'a,T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 pub fn run_queue_tests<T: Queue<char>>(queue: &mut T) {
     for iter in 0..3 {
         assert_eq!(queue.head(), None);
@@ -129,7 +315,7 @@ pub fn run_queue_tests<T: Queue<char>>(queue: &mut T) {
         queue.enqueue('D');
         queue.enqueue('E');
 
-        //assert_eq!(queue.len(), 5);
+        assert_eq!(queue.len(), 5);
         assert_eq!(queue.head(), Some(&'A'));
         assert_eq!(queue.tail(), Some(&'E'));
 
@@ -142,13 +328,16 @@ pub fn run_queue_tests<T: Queue<char>>(queue: &mut T) {
         assert_eq!(queue.dequeue(), Some('F'));
         assert_eq!(queue.dequeue(), None);
     }
-    //assert_eq!(queue.len(), 0);
+    assert_eq!(queue.len(), 0);
+    assert!(queue.is_empty());
 }
 
 #[cfg(test)]
 mod test {
     use super::run_queue_tests;
     use super::DoubleStackQueue;
+    use super::OverflowPolicy;
+    use super::QueueFullError;
     use super::Queue;
 
     #[test]
@@ -168,4 +357,70 @@ mod test {
             counter += 1;
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn double_stack_queue_iter_mut() {
+        let mut queue = DoubleStackQueue::from([1, 2, 3]);
+        queue.dequeue();
+        queue.enqueue_values([4, 5]);
+        // saida: [3, 2], entrada: [4, 5]
+        for value in queue.iter_mut() {
+            *value *= 10;
+        }
+        for value in &mut queue {
+            *value += 1;
+        }
+        assert_eq!((&queue).into_iter().copied().collect::<Vec<_>>(), vec![21, 31, 41, 51]);
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.capacity(), None);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![21, 31, 41, 51]);
+    }
+
+    #[test]
+    pub fn double_stack_queue_bounded() {
+        // Histórico dos últimos 3
+        let mut history = DoubleStackQueue::bounded(3, OverflowPolicy::EvictOldest);
+        assert_eq!(history.capacity(), Some(3));
+        assert_eq!(history.try_enqueue('A'), Ok(None));
+        history.enqueue_values(['B','C']);
+        assert_eq!(history.try_enqueue('D'), Ok(Some('A')));
+        history.enqueue('E');
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().collect::<String>(), "CDE");
+        assert_eq!(history.dequeue(), Some('C'));
+        history.enqueue('F');
+        assert_eq!(history.into_iter().collect::<String>(), "DEF");
+
+        let mut queue = DoubleStackQueue::bounded(2, OverflowPolicy::Reject);
+        queue.enqueue('A');
+        queue.enqueue('B');
+        assert_eq!(queue.try_enqueue('C'), Err(QueueFullError { value: 'C' }));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dequeue(), Some('A'));
+        assert_eq!(queue.try_enqueue('C'), Ok(None));
+        assert_eq!(queue.iter().collect::<String>(), "BC");
+        queue.clear();
+        run_queue_tests(&mut DoubleStackQueue::bounded(5, OverflowPolicy::Reject));
+
+        // Pela trait, a mesma fila não entra em panic quando cheia
+        fn encher<Q: Queue<char>>(queue: &mut Q, values: &str) -> Vec<Result<Option<char>, QueueFullError<char>>> {
+            return values.chars().map(|c| queue.try_enqueue(c)).collect();
+        }
+        let mut queue = DoubleStackQueue::bounded(2, OverflowPolicy::Reject);
+        assert_eq!(encher(&mut queue, "ABC"), vec![Ok(None), Ok(None), Err(QueueFullError { value: 'C' })]);
+        let mut history = DoubleStackQueue::bounded(2, OverflowPolicy::EvictOldest);
+        assert_eq!(encher(&mut history, "ABC"), vec![Ok(None), Ok(None), Ok(Some('A'))]);
+        assert_eq!(encher(&mut DoubleStackQueue::new(), "AB"), vec![Ok(None), Ok(None)]);
+
+        let mut zero = DoubleStackQueue::bounded(0, OverflowPolicy::EvictOldest);
+        assert_eq!(zero.try_enqueue(1), Ok(Some(1)));
+        assert!(zero.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "DoubleStackQueue: fila cheia (capacidade 1)")]
+    pub fn double_stack_queue_bounded_panic() {
+        let mut queue = DoubleStackQueue::bounded(1, OverflowPolicy::Reject);
+        queue.enqueue_values(['A','B']);
+    }
+}
@@ -1,5 +1,5 @@
 // https://rust-unofficial.github.io/too-many-lists/
-use std::{fmt, rc::Rc};
+use std::{fmt, ops::Deref, rc::Rc};
 
 pub trait Stack<T> {
     /**
@@ -51,12 +51,18 @@ impl<T: fmt::Debug> fmt::Debug for Node<T> {
  * Single Linked List
  * A ideia é ser uma estrutura estilo pilha, e leve de se criar cópias (uma cópia é só uma referência ao último elemento)
  */
-#[derive(Clone)]
 pub struct LinkedStack<T> {
     head: Link<T>,
     length: i32
 }
 
+// Cópia é só uma nova referência ao topo, não precisa de T: Clone
+impl<T> Clone for LinkedStack<T> {
+    fn clone(&self) -> Self {
+        LinkedStack { head: self.head.clone(), length: self.length }
+    }
+}
+
 impl<T> LinkedStack<T> {
     pub fn new() -> LinkedStack<T> {
         LinkedStack { head: None, length: 0 }
@@ -75,13 +81,11 @@ impl<T> LinkedStack<T> {
     pub fn len(&self) -> i32 {
         self.length
     }
-}
 
-impl<T> Stack<T> for LinkedStack<T> 
-where 
-    T: Clone
-{
-    fn push(&mut self, value: T) {
+    /**
+     * Mesmo que Stack::push, mas disponível para qualquer T (mesmo sem Clone)
+     */
+    pub fn push(&mut self, value: T) {
         // Necessário usar Option.take() para obter o valor ao mesmo tempo que transforma ele em None
         // Isso é para o valor em nenhum instante ter dois donos, e evitar fazer uma nova cópia
         self.head = Some(LinkedStack::node(
@@ -91,25 +95,104 @@ where
         self.length += 1;
     }
 
-    fn pop(&mut self) -> Option<T> {
-        match self.head.take() {
-            Some(first) => {
-                self.head = first.next.clone();
-                self.length -= 1;
-                return Some(first.value.clone());
-            },
+    /**
+     * Mesmo que Stack::peek, mas disponível para qualquer T (mesmo sem Clone)
+     */
+    pub fn peek(&self) -> Option<&T> {
+        match self.head.as_ref() {
+            Some(first) => { return Some(&first.value); },
             None => { return None },
         }
     }
 
+    /**
+     * Remove o topo sem precisar de Clone, retornando uma referência compartilhada (Rc) ao valor
+     * 
+     * Se o nó não era compartilhado com nenhuma cópia, StackRef::try_unwrap consegue mover o valor para fora
+     */
+    pub fn pop_shared(&mut self) -> Option<StackRef<T>> {
+        let mut first = self.head.take()?;
+        // Se ninguém mais aponta para o nó, tira o next dele, assim o StackRef não segura o resto da pilha
+        self.head = match Rc::get_mut(&mut first) {
+            Some(node) => node.next.take(),
+            None => first.next.clone(),
+        };
+        self.length -= 1;
+        return Some(StackRef { node: Some(first) });
+    }
+}
+
+// Para T sem Clone não tem Stack::pop, usar pop_shared (e StackRef::try_unwrap para mover o valor)
+impl<T> Stack<T> for LinkedStack<T> 
+where 
+    T: Clone
+{
+    fn push(&mut self, value: T) {
+        LinkedStack::push(self, value);
+    }
+
+    /**
+     * Move o valor para fora se o nó não é compartilhado com nenhuma cópia (Rc::try_unwrap),
+     * só clona quando outra cópia da pilha ainda aponta para o nó
+     */
+    fn pop(&mut self) -> Option<T> {
+        let first = self.pop_shared()?;
+        match first.try_unwrap() {
+            Ok(value) => { return Some(value); },
+            Err(shared) => { return Some(shared.deref().clone()); },
+        }
+    }
+
     fn peek(&self) -> Option<&T> {
-        match self.head.as_ref() {
-            Some(first) => { return Some(&first.value); },
-            None => { return None },
+        LinkedStack::peek(self)
+    }
+}
+
+/**
+ * Valor removido com pop_shared, funciona como um &T (Deref) mas mantêm o nó vivo via Rc
+ */
+pub struct StackRef<T> {
+    // Sempre Some, Option somente para o Drop conseguir tirar o nó
+    node: Option<Rc<Node<T>>>
+}
+
+impl<T> StackRef<T> {
+    /**
+     * Move o valor para fora se esta for a única referência ao nó, senão devolve o próprio StackRef
+     */
+    pub fn try_unwrap(mut self) -> Result<T, StackRef<T>> {
+        let node = self.node.take().unwrap();
+        match Rc::try_unwrap(node) {
+            Ok(mut node) => {
+                // Descarta o resto de forma iterativa antes de mover o valor
+                drop(LinkedStack { head: node.next.take(), length: 0 });
+                return Ok(node.value);
+            },
+            Err(node) => { return Err(StackRef { node: Some(node) }); },
         }
     }
 }
 
+impl<T> Deref for StackRef<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.node.as_ref().unwrap().value
+    }
+}
+
+// Se este for o último dono do nó, o Drop do LinkedStack descarta o resto sem recursão
+impl<T> Drop for StackRef<T> {
+    fn drop(&mut self) {
+        drop(LinkedStack { head: self.node.take(), length: 0 });
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StackRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StackRef({:?})", self.deref())
+    }
+}
+
 /**
  * You don't actually need to implement Drop if you contain types that implement Drop, and all you'd want to do is call their destructors.
  * All that's handled for us automatically... with one hitch.
@@ -256,4 +339,78 @@ mod test {
 
         assert_eq!(stack.len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn pop_without_clone() {
+        // Não implementa Clone
+        #[derive(Debug, PartialEq)]
+        struct Arquivo(String);
+
+        let mut stack = LinkedStack::new();
+        stack.push(Arquivo("a.txt".to_string()));
+        stack.push(Arquivo("b.txt".to_string()));
+        assert_eq!(stack.peek(), Some(&Arquivo("b.txt".to_string())));
+
+        let b = stack.pop_shared().unwrap();
+        assert_eq!(b.0, "b.txt");
+        assert_eq!(stack.len(), 1);
+        assert_eq!(b.try_unwrap().ok(), Some(Arquivo("b.txt".to_string())));
+
+        // Compartilhado com uma cópia: não dá para mover, mas dá para ler
+        let copia = stack.clone();
+        let a = stack.pop_shared().unwrap();
+        assert_eq!(format!("{:?}", a), "StackRef(Arquivo(\"a.txt\"))");
+        let a = a.try_unwrap().unwrap_err();
+        drop(copia);
+        assert_eq!(a.try_unwrap().ok(), Some(Arquivo("a.txt".to_string())));
+        assert!(stack.pop_shared().is_none());
+    }
+
+    #[test]
+    pub fn pop_clones_only_shared() {
+        use std::{cell::Cell, rc::Rc};
+
+        struct Contador(Rc<Cell<i32>>);
+        impl Clone for Contador {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Contador(self.0.clone())
+            }
+        }
+
+        let clones = Rc::new(Cell::new(0));
+        let mut stack = LinkedStack::new();
+        for _ in 0..4 {
+            stack.push(Contador(clones.clone()));
+        }
+
+        // Ninguém compartilha os nós: move sem clonar
+        stack.pop();
+        assert_eq!(clones.get(), 0);
+
+        // Os 3 nós restantes são compartilhados com a cópia
+        let copia = stack.clone();
+        stack.pop();
+        stack.pop();
+        assert_eq!(clones.get(), 2);
+        drop(copia);
+        stack.pop();
+        assert_eq!(clones.get(), 2);
+        assert_eq!(stack.len(), 0);
+    }
+
+    #[test]
+    pub fn pop_shared_destructors() {
+        let mut stack = LinkedStack::new();
+        for i in 0..1_000_000 {
+            stack.push(i);
+        }
+        let copia = stack.clone();
+        // O topo segura o resto da pilha (compartilhado com a cópia), e é o último a ser descartado
+        let topo = stack.pop_shared().unwrap();
+        drop(stack);
+        drop(copia);
+        assert_eq!(*topo, 999_999);
+        drop(topo);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{fmt, ops::Deref, sync::Arc};
+
+use super::Stack;
+
+type SyncLink<T> = Option<Arc<SyncNode<T>>>;
+
+struct SyncNode<T> {
+    value: T,
+    next: SyncLink<T>
+}
+
+/**
+ * Mesma ideia do LinkedStack, mas com Arc no lugar de Rc
+ *
+ * Com Rc o contador de referências não é atômico, então a pilha não pode ser enviada para outra thread.
+ * Com Arc, SyncLinkedStack<T> é Send + Sync sempre que T for Send + Sync, assim várias threads podem
+ * continuar a partir do mesmo prefixo (ex: caminhos de uma busca em paralelo) sem copiar os nós compartilhados.
+ *
+ * O custo é o incremento/decremento atômico ao clonar e descartar nós
+ */
+pub struct SyncLinkedStack<T> {
+    head: SyncLink<T>,
+    length: usize
+}
+
+// Clonar só copia o Arc do topo, então não precisa de T: Clone
+impl<T> Clone for SyncLinkedStack<T> {
+    fn clone(&self) -> Self {
+        SyncLinkedStack { head: self.head.clone(), length: self.length }
+    }
+}
+
+impl<T> SyncLinkedStack<T> {
+    pub fn new() -> SyncLinkedStack<T> {
+        SyncLinkedStack { head: None, length: 0 }
+    }
+
+    fn node(value: T, next: SyncLink<T>) -> Arc<SyncNode<T>> {
+        Arc::new(SyncNode { value: value, next: next })
+    }
+
+    pub fn iter(&self) -> SyncLinkedStackIter<'_, T> {
+        SyncLinkedStackIter {
+            atual: self.head.as_ref()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /**
+     * Mesmo que Stack::push, mas disponível para qualquer T (mesmo sem Clone)
+     */
+    pub fn push(&mut self, value: T) {
+        self.head = Some(SyncLinkedStack::node(
+            value,
+            self.head.take()
+        ));
+        self.length += 1;
+    }
+
+    /**
+     * Mesmo que Stack::peek, mas disponível para qualquer T (mesmo sem Clone)
+     */
+    pub fn peek(&self) -> Option<&T> {
+        match self.head.as_ref() {
+            Some(first) => { return Some(&first.value); },
+            None => { return None },
+        }
+    }
+
+    /**
+     * Igual ao LinkedStack::pop_shared: remove o topo sem precisar de Clone, retornando uma referência (Arc) ao valor
+     *
+     * Se o nó não era compartilhado com nenhuma cópia, SyncStackRef::try_unwrap consegue mover o valor para fora
+     */
+    pub fn pop_shared(&mut self) -> Option<SyncStackRef<T>> {
+        let mut first = self.head.take()?;
+        // Se ninguém mais aponta para o nó, tira o next dele, assim o SyncStackRef não segura o resto da pilha
+        self.head = match Arc::get_mut(&mut first) {
+            Some(node) => node.next.take(),
+            None => first.next.clone(),
+        };
+        self.length -= 1;
+        return Some(SyncStackRef { node: Some(first) });
+    }
+}
+
+impl<T> Default for SyncLinkedStack<T> {
+    fn default() -> Self {
+        SyncLinkedStack::new()
+    }
+}
+
+// Para T sem Clone não tem Stack::pop, usar pop_shared (e SyncStackRef::try_unwrap para mover o valor)
+impl<T> Stack<T> for SyncLinkedStack<T>
+where
+    T: Clone
+{
+    fn push(&mut self, value: T) {
+        SyncLinkedStack::push(self, value);
+    }
+
+    /**
+     * Igual ao LinkedStack: move o valor se o nó não é compartilhado, só clona quando é
+     */
+    fn pop(&mut self) -> Option<T> {
+        let first = self.pop_shared()?;
+        match first.try_unwrap() {
+            Ok(value) => { return Some(value); },
+            Err(shared) => { return Some(shared.deref().clone()); },
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SyncLinkedStack::peek(self)
+    }
+}
+
+/**
+ * Valor removido com pop_shared, funciona como um &T (Deref) mas mantêm o nó vivo via Arc
+ * Pode ser enviado para outra thread, assim como o SyncLinkedStack
+ */
+pub struct SyncStackRef<T> {
+    // Sempre Some, Option somente para o Drop conseguir tirar o nó
+    node: SyncLink<T>
+}
+
+impl<T> SyncStackRef<T> {
+    /**
+     * Move o valor para fora se esta for a única referência ao nó, senão devolve o próprio SyncStackRef
+     */
+    pub fn try_unwrap(mut self) -> Result<T, SyncStackRef<T>> {
+        let node = self.node.take().unwrap();
+        match Arc::try_unwrap(node) {
+            Ok(mut node) => {
+                // Descarta o resto de forma iterativa antes de mover o valor
+                drop(SyncLinkedStack { head: node.next.take(), length: 0 });
+                return Ok(node.value);
+            },
+            Err(node) => { return Err(SyncStackRef { node: Some(node) }); },
+        }
+    }
+}
+
+impl<T> Deref for SyncStackRef<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.node.as_ref().unwrap().value
+    }
+}
+
+// Se este for o último dono do nó, o Drop do SyncLinkedStack descarta o resto sem recursão
+impl<T> Drop for SyncStackRef<T> {
+    fn drop(&mut self) {
+        drop(SyncLinkedStack { head: self.node.take(), length: 0 });
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SyncStackRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncStackRef({:?})", self.deref())
+    }
+}
+
+/**
+ * Drop iterativo, igual ao LinkedStack, para não estourar a pilha com listas grandes
+ * Arc::try_unwrap só tem sucesso se esta for a última referência ao nó, então
+ * para no primeiro nó que ainda é compartilhado com outra cópia (possivelmente em outra thread)
+ */
+impl<T> Drop for SyncLinkedStack<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            if let Ok(mut node) = Arc::try_unwrap(node) {
+                head = node.next.take();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SyncLinkedStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncLinkedStack [length:{}] ", self.len())?;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct SyncLinkedStackIter<'a,T> {
+    atual: Option<&'a Arc<SyncNode<T>>>
+}
+
+impl<'a,T> Iterator for SyncLinkedStackIter<'a,T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let atual = self.atual?;
+        self.atual = atual.next.as_ref();
+        Some(&atual.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use crate::estruturas::{run_stack_tests, Stack};
+
+    use super::SyncLinkedStack;
+
+    #[test]
+    pub fn sync_linked_stack() {
+        let mut stack = SyncLinkedStack::new();
+        run_stack_tests(&mut stack);
+    }
+
+    #[test]
+    pub fn sync_shared_prefix() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SyncLinkedStack<char>>();
+
+        let mut prefix = SyncLinkedStack::new();
+        prefix.push_values(['A','B','C']);
+
+        // Cada thread continua o caminho a partir do mesmo prefixo
+        let handles: Vec<_> = ['X','Y','Z'].into_iter().map(|c| {
+            let mut path = prefix.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    path.push(c);
+                }
+                let mut path: Vec<char> = path.iter().copied().collect();
+                path.dedup();
+                path.into_iter().collect::<String>()
+            })
+        }).collect();
+
+        let paths: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(paths, vec!["XCBA", "YCBA", "ZCBA"]);
+
+        // O prefixo original não foi alterado
+        assert_eq!(prefix.len(), 3);
+        assert_eq!(prefix.pop(), Some('C'));
+        assert_eq!(format!("{:?}", prefix), "SyncLinkedStack [length:2] ['B', 'A']");
+    }
+
+    #[test]
+    pub fn sync_destructors() {
+        let mut stack = SyncLinkedStack::new();
+        let size = 1_000_000;
+        for i in 0..size {
+            stack.push(i);
+        }
+        let copia = stack.clone();
+        assert_eq!(stack.len(), size);
+
+        // Descarta em outra thread enquanto a cópia ainda segura os nós
+        thread::spawn(move || drop(stack)).join().unwrap();
+        assert_eq!(copia.iter().count(), size);
+        drop(copia);
+
+        // Clonar funciona com valores que não são Clone
+        struct NotClone(i32);
+        let mut stack = SyncLinkedStack::new();
+        stack.push(NotClone(7));
+        let copia = stack.clone();
+        assert_eq!(copia.iter().next().map(|v| v.0), Some(7));
+    }
+
+    #[test]
+    pub fn sync_pop_shared() {
+        // Não implementa Clone
+        #[derive(Debug, PartialEq)]
+        struct Arquivo(String);
+
+        let mut stack = SyncLinkedStack::default();
+        assert!(stack.is_empty());
+        stack.push(Arquivo("a.txt".to_string()));
+        stack.push(Arquivo("b.txt".to_string()));
+        assert_eq!(stack.peek(), Some(&Arquivo("b.txt".to_string())));
+
+        let b = stack.pop_shared().unwrap();
+        assert_eq!(stack.len(), 1);
+        assert_eq!(b.try_unwrap().ok(), Some(Arquivo("b.txt".to_string())));
+
+        // Compartilhado com uma cópia em outra thread: não dá para mover até a cópia ser descartada
+        let copia = stack.clone();
+        let a = stack.pop_shared().unwrap();
+        assert_eq!(format!("{:?}", a), "SyncStackRef(Arquivo(\"a.txt\"))");
+        let a = a.try_unwrap().unwrap_err();
+        thread::spawn(move || drop(copia)).join().unwrap();
+        let a = thread::spawn(move || a.try_unwrap().ok()).join().unwrap();
+        assert_eq!(a, Some(Arquivo("a.txt".to_string())));
+        assert!(stack.pop_shared().is_none());
+        assert!(stack.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+/**
+ * Conjuntos disjuntos (Union-Find / Disjoint Set Union) com elementos 0..len
+ *
+ * Funcionamento:
+ * - Cada conjunto é uma árvore, parent[x] aponta para o pai de x e a raiz é o 'representante' do conjunto
+ * - find(x): sobe até a raiz, e no caminho faz cada nó apontar direto para ela (compressão de caminho)
+ * - union(a, b): pendura a raiz do conjunto menor na raiz do maior (união por tamanho)
+ *
+ * Com as duas otimizações cada operação é praticamente O(1) amortizado
+ */
+#[derive(Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    // Tamanho do conjunto, só é válido nas raízes
+    size: Vec<usize>,
+    sets: usize
+}
+
+impl UnionFind {
+    /**
+     * Cada elemento de 0..len começa no seu próprio conjunto
+     */
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind { parent: (0..len).collect(), size: vec![1; len], sets: len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /**
+     * Adiciona um novo elemento em um conjunto só dele, retorna o elemento
+     */
+    pub fn push(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.size.push(1);
+        self.sets += 1;
+        return x;
+    }
+
+    /**
+     * Representante do conjunto de x
+     */
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Compressão de caminho
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        return root;
+    }
+
+    /**
+     * Junta os conjuntos de a e b, retorna false se já estavam no mesmo conjunto
+     */
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b { return false; }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        return true;
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /**
+     * Quantidade de elementos no conjunto de x
+     */
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        return self.size[root];
+    }
+
+    /**
+     * Quantidade de conjuntos distintos
+     */
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+}
+
+impl fmt::Debug for UnionFind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UnionFind [length:{}, sets:{}]", self.len(), self.sets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UnionFind;
+
+    #[test]
+    pub fn union_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.set_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.set_size(2), 4);
+        assert_eq!(sets.set_size(5), 1);
+        assert_eq!(sets.set_count(), 3);
+
+        let x = sets.push();
+        assert_eq!(x, 6);
+        sets.union(x, 5);
+        assert!(sets.connected(6, 5));
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(format!("{:?}", sets), "UnionFind [length:7, sets:3]");
+
+        // Uma corrente longa fica com caminhos curtos depois do find
+        let mut sets = UnionFind::new(1000);
+        for i in 1..1000 {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.set_count(), 1);
+        let root = sets.find(999);
+        assert!((0..1000).all(|i| sets.parent[i] == root || sets.parent[sets.parent[i]] == root));
+    }
+}
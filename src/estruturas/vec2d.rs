@@ -39,14 +39,299 @@ impl<T> Vec2D<T> where T: Clone {
     pub fn len(&self) -> usize {
         return self.width * self.height;
     }
+}
+
+impl<T> Vec2D<T> {
+    pub fn in_bounds(&self, (x,y): (i32,i32)) -> bool {
+        // Compara como usize (x e y já são >= 0), width as i32 daria negativo com mais de i32::MAX colunas
+        return x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height;
+    }
 
     pub fn get(&self, (x,y): (i32,i32)) -> Option<&T> {
-        let (w,h) = (self.width as i32, self.height as i32);
-        if x < 0 || y < 0 || x >= w || y >= h {
+        if !self.in_bounds((x,y)) {
+            return None;
+        }
+
+        return self.data.get(y as usize * self.width + x as usize);
+    }
+
+    pub fn get_mut(&mut self, (x,y): (i32,i32)) -> Option<&mut T> {
+        if !self.in_bounds((x,y)) {
             return None;
         }
 
-        return self.data.get((y * w + x) as usize);
+        return self.data.get_mut(y as usize * self.width + x as usize);
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        if y >= self.height { panic!("Linha fora dos limites: {}", y); }
+
+        return &self.data[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        if y >= self.height { panic!("Linha fora dos limites: {}", y); }
+
+        return &mut self.data[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        if x >= self.width { panic!("Coluna fora dos limites: {}", x); }
+
+        return self.data.iter().skip(x).step_by(self.width);
+    }
+
+    /**
+     * Igual ao values(), junto com a posição de cada valor
+     */
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize,usize), &T)> + '_ {
+        return Iterator2D::xy((self.width, self.height)).zip(self.data.iter());
+    }
+
+    /**
+     * Retângulo ((x, y), (largura, altura)) dentro do Vec2D, as posições na view começam em (0,0) no canto do retângulo
+     */
+    pub fn view(&self, rect: ((usize,usize),(usize,usize))) -> Vec2DView<'_, T> {
+        self.check_rect(rect);
+        return Vec2DView { vec: self, pos: rect.0, size: rect.1 };
+    }
+
+    pub fn view_mut(&mut self, rect: ((usize,usize),(usize,usize))) -> Vec2DViewMut<'_, T> {
+        self.check_rect(rect);
+        return Vec2DViewMut { vec: self, pos: rect.0, size: rect.1 };
+    }
+
+    fn check_rect(&self, ((x,y),(w,h)): ((usize,usize),(usize,usize))) {
+        // checked_add: x + w pode estourar o usize
+        if x.checked_add(w).is_none_or(|r| r > self.width) || y.checked_add(h).is_none_or(|b| b > self.height) {
+            panic!("Retângulo fora dos limites: {:?}", ((x,y),(w,h)));
+        }
+    }
+
+    /**
+     * Vizinhos de cima, esquerda, direita e baixo (nesta ordem)
+     */
+    pub fn neighbors4<'a>(&'a self, pos: (i32,i32), edge: EdgePolicy<'a, T>) -> Neighbors<'a, T> {
+        return Neighbors { vec: self, pos: pos, edge: edge, offsets: &NEIGHBORS4, index: 0 };
+    }
+
+    /**
+     * Os 8 vizinhos, incluindo diagonais, linha por linha de cima para baixo:
+     * 0 1 2
+     * 3 . 4
+     * 5 6 7
+     */
+    pub fn neighbors8<'a>(&'a self, pos: (i32,i32), edge: EdgePolicy<'a, T>) -> Neighbors<'a, T> {
+        return Neighbors { vec: self, pos: pos, edge: edge, offsets: &NEIGHBORS8, index: 0 };
+    }
+}
+
+const NEIGHBORS4: [(i32,i32); 4] = [(0,-1), (-1,0), (1,0), (0,1)];
+const NEIGHBORS8: [(i32,i32); 8] = [(-1,-1), (0,-1), (1,-1), (-1,0), (1,0), (-1,1), (0,1), (1,1)];
+
+/**
+ * O que fazer com vizinhos fora dos limites do Vec2D
+ */
+#[derive(Debug, PartialEq, Eq)]
+pub enum EdgePolicy<'a, T> {
+    // Não retorna
+    Clip,
+    // Continua do outro lado (toroidal), a posição retornada é a do outro lado
+    Wrap,
+    // Retorna este valor, com a posição fora dos limites
+    Default(&'a T)
+}
+
+// Só guarda uma referência, então é Copy mesmo que T não seja (o derive exigiria T: Copy)
+impl<T> Clone for EdgePolicy<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EdgePolicy<'_, T> {}
+
+/**
+ * Iterador do neighbors4/neighbors8, retorna (posição, valor)
+ */
+pub struct Neighbors<'a, T> {
+    vec: &'a Vec2D<T>,
+    pos: (i32,i32),
+    edge: EdgePolicy<'a, T>,
+    offsets: &'static [(i32,i32)],
+    index: usize
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = ((i32,i32), &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((ox,oy)) = self.offsets.get(self.index) {
+            self.index += 1;
+            // Em i64 para não estourar perto de i32::MAX, vizinhos que não cabem em i32 são ignorados
+            let (nx, ny) = (self.pos.0 as i64 + *ox as i64, self.pos.1 as i64 + *oy as i64);
+            let neigh = (i32::try_from(nx), i32::try_from(ny));
+            if let (Ok(x), Ok(y)) = neigh && let Some(value) = self.vec.get((x,y)) {
+                return Some(((x,y), value));
+            }
+
+            match self.edge {
+                EdgePolicy::Clip => continue,
+                EdgePolicy::Wrap => {
+                    // Vec2D vazio não tem nenhuma posição para dar a volta
+                    if self.vec.width == 0 || self.vec.height == 0 { return None; }
+
+                    let wrapped = (nx.rem_euclid(self.vec.width as i64), ny.rem_euclid(self.vec.height as i64));
+                    if let (Ok(x), Ok(y)) = (i32::try_from(wrapped.0), i32::try_from(wrapped.1)) {
+                        return self.vec.get((x,y)).map(|value| ((x,y), value));
+                    }
+                },
+                EdgePolicy::Default(value) => {
+                    if let (Ok(x), Ok(y)) = neigh {
+                        return Some(((x,y), value));
+                    }
+                },
+            }
+        }
+        return None;
+    }
+}
+
+/**
+ * Retângulo dentro de um Vec2D, emprestado com view()
+ */
+pub struct Vec2DView<'a, T> {
+    vec: &'a Vec2D<T>,
+    pos: (usize,usize),
+    size: (usize,usize)
+}
+
+/**
+ * Retângulo dentro de um Vec2D, emprestado com view_mut()
+ */
+pub struct Vec2DViewMut<'a, T> {
+    vec: &'a mut Vec2D<T>,
+    pos: (usize,usize),
+    size: (usize,usize)
+}
+
+// Linhas do retângulo, cada uma é uma fatia de uma linha do Vec2D
+fn rect_rows<T>(data: &[T], width: usize, (x,y): (usize,usize), (w,h): (usize,usize)) -> impl Iterator<Item = &[T]> {
+    return data.chunks(width.max(1)).skip(y).take(h).map(move |row| &row[x..x + w]);
+}
+
+impl<'a, T> Vec2DView<'a, T> {
+    pub fn size(&self) -> (usize,usize) {
+        return self.size;
+    }
+
+    pub fn get(&self, (x,y): (i32,i32)) -> Option<&'a T> {
+        if x < 0 || y < 0 || x as usize >= self.size.0 || y as usize >= self.size.1 {
+            return None;
+        }
+
+        return Some(&self.vec[(self.pos.0 + x as usize, self.pos.1 + y as usize)]);
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        if y >= self.size.1 { panic!("Linha fora dos limites: {}", y); }
+
+        return &self.vec.row(self.pos.1 + y)[self.pos.0..self.pos.0 + self.size.0];
+    }
+
+    pub fn positions(&self) -> Iterator2D {
+        return Iterator2D::xy(self.size);
+    }
+
+    /**
+     * Valores linha por linha
+     */
+    pub fn values(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        return rect_rows(&self.vec.data, self.vec.width, self.pos, self.size).flatten();
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize,usize), &'a T)> + use<'a, T> {
+        return Iterator2D::xy(self.size).zip(self.values());
+    }
+}
+
+impl<T> Vec2DViewMut<'_, T> {
+    pub fn size(&self) -> (usize,usize) {
+        return self.size;
+    }
+
+    pub fn get(&self, (x,y): (i32,i32)) -> Option<&T> {
+        return self.as_view().get((x,y));
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return self.as_view().row(y);
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        if y >= self.size.1 { panic!("Linha fora dos limites: {}", y); }
+
+        let (x, w) = (self.pos.0, self.size.0);
+        return &mut self.vec.row_mut(self.pos.1 + y)[x..x + w];
+    }
+
+    pub fn positions(&self) -> Iterator2D {
+        return Iterator2D::xy(self.size);
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        return self.as_view().values();
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let ((x,y),(w,h)) = (self.pos, self.size);
+        return self.vec.data.chunks_mut(self.vec.width.max(1)).skip(y).take(h).flat_map(move |row| row[x..x + w].iter_mut());
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize,usize), &T)> {
+        return self.as_view().enumerate();
+    }
+
+    /**
+     * Preenche o retângulo inteiro com value
+     */
+    pub fn fill(&mut self, value: T) where T: Clone {
+        for v in self.values_mut() {
+            *v = value.clone();
+        }
+    }
+
+    fn as_view(&self) -> Vec2DView<'_, T> {
+        return Vec2DView { vec: self.vec, pos: self.pos, size: self.size };
+    }
+}
+
+// let value = view[(x, y)];
+impl<T> Index<(usize, usize)> for Vec2DView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        if x >= self.size.0 || y >= self.size.1 { panic!("Índice fora dos limites: {:?}",(x,y)); }
+
+        &self.vec[(self.pos.0 + x, self.pos.1 + y)]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Vec2DViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        if x >= self.size.0 || y >= self.size.1 { panic!("Índice fora dos limites: {:?}",(x,y)); }
+
+        &self.vec[(self.pos.0 + x, self.pos.1 + y)]
+    }
+}
+
+// view[(x, y)] = value;
+impl<T> IndexMut<(usize, usize)> for Vec2DViewMut<'_, T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        if x >= self.size.0 || y >= self.size.1 { panic!("Índice fora dos limites: {:?}",(x,y)); }
+
+        &mut self.vec[(self.pos.0 + x, self.pos.1 + y)]
     }
 }
 
@@ -98,4 +383,111 @@ impl Iterator for Iterator2D {
 
         return Some(_pos);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EdgePolicy, Vec2D};
+
+    #[test]
+    pub fn vec2d_neighbors() {
+        // 0 1 2
+        // 3 4 5
+        let mut vec = Vec2D::new(3, 2, 0);
+        for (i, pos) in vec.positions().enumerate() {
+            vec[pos] = i;
+        }
+        assert!(vec.in_bounds((2, 1)));
+        assert!(!vec.in_bounds((3, 1)));
+        assert!(!vec.in_bounds((0, -1)));
+        *vec.get_mut((1, 1)).unwrap() = 40;
+        assert_eq!(vec[(1, 1)], 40);
+        assert_eq!(vec.get_mut((-1, 0)), None);
+        vec[(1, 1)] = 4;
+
+        let values = |iter: super::Neighbors<usize>| iter.map(|(_, v)| *v).collect::<Vec<usize>>();
+        assert_eq!(values(vec.neighbors4((1, 0), EdgePolicy::Clip)), vec![0, 2, 4]);
+        assert_eq!(values(vec.neighbors8((1, 0), EdgePolicy::Clip)), vec![0, 2, 3, 4, 5]);
+        assert_eq!(values(vec.neighbors8((0, 0), EdgePolicy::Wrap)), vec![5, 3, 4, 2, 1, 5, 3, 4]);
+        assert_eq!(values(vec.neighbors4((2, 1), EdgePolicy::Default(&9))), vec![2, 4, 9, 9]);
+
+        // Posições: a do outro lado no Wrap, fora dos limites no Default
+        let positions: Vec<(i32, i32)> = vec.neighbors4((0, 0), EdgePolicy::Wrap).map(|(pos, _)| pos).collect();
+        assert_eq!(positions, vec![(0, 1), (2, 0), (1, 0), (0, 1)]);
+        let positions: Vec<(i32, i32)> = vec.neighbors4((0, 0), EdgePolicy::Default(&9)).map(|(pos, _)| pos).collect();
+        assert_eq!(positions, vec![(0, -1), (-1, 0), (1, 0), (0, 1)]);
+
+        // Contar vizinhos como no jogo da vida
+        let life = Vec2D::new(4, 4, true);
+        assert_eq!(life.neighbors8((0, 0), EdgePolicy::Wrap).filter(|(_, v)| **v).count(), 8);
+        assert_eq!(life.neighbors8((0, 0), EdgePolicy::Clip).filter(|(_, v)| **v).count(), 3);
+
+        // Vazio: nenhum vizinho, mesmo com Wrap
+        let empty: Vec2D<usize> = Vec2D::new(0, 3, 0);
+        assert_eq!(values(empty.neighbors8((0, 0), EdgePolicy::Wrap)), vec![]);
+        assert_eq!(values(Vec2D::new(2, 0, 0).neighbors4((1, 0), EdgePolicy::Wrap)), vec![]);
+
+        // Limites do i32: com () não aloca nada, mesmo com mais de i32::MAX colunas
+        let wide = Vec2D::new(3_000_000_000, 1, ());
+        assert!(wide.in_bounds((i32::MAX, 0)));
+        assert!(!wide.in_bounds((0, 1)));
+        let positions: Vec<(i32, i32)> = wide.neighbors4((i32::MAX, 0), EdgePolicy::Default(&())).map(|(pos, _)| pos).collect();
+        assert_eq!(positions, vec![(i32::MAX, -1), (i32::MAX - 1, 0), (i32::MAX, 1)]);
+        let small = Vec2D::new(2, 2, ());
+        let positions: Vec<(i32, i32)> = small.neighbors4((i32::MAX, i32::MIN), EdgePolicy::Wrap).map(|(pos, _)| pos).collect();
+        assert_eq!(positions, vec![(1, 1), (0, 0), (0, 0), (1, 1)]);
+    }
+
+    #[test]
+    pub fn vec2d_views() {
+        // 0 1 2 3
+        // 4 5 6 7
+        // 8 9 10 11
+        let mut vec = Vec2D::new(4, 3, 0);
+        for (i, pos) in vec.positions().enumerate() {
+            vec[pos] = i;
+        }
+        assert_eq!(vec.row(1), &[4, 5, 6, 7]);
+        assert_eq!(vec.column(2).copied().collect::<Vec<usize>>(), vec![2, 6, 10]);
+        assert_eq!(vec.enumerate().nth(5), Some(((1, 1), &5)));
+        vec.row_mut(0).reverse();
+        assert_eq!(vec.row(0), &[3, 2, 1, 0]);
+        vec.row_mut(0).reverse();
+
+        let view = vec.view(((1, 1), (2, 2)));
+        assert_eq!(view.size(), (2, 2));
+        assert_eq!(view[(0, 0)], 5);
+        assert_eq!(view[(1, 1)], 10);
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.row(1), &[9, 10]);
+        assert_eq!(view.values().copied().collect::<Vec<usize>>(), vec![5, 6, 9, 10]);
+        assert_eq!(view.enumerate().last(), Some(((1, 1), &10)));
+
+        let mut view = vec.view_mut(((2, 0), (2, 3)));
+        view[(0, 2)] = 100;
+        view.row_mut(0)[1] = 30;
+        for v in view.values_mut() {
+            *v += 1;
+        }
+        assert_eq!(view.values().copied().collect::<Vec<usize>>(), vec![3, 31, 7, 8, 101, 12]);
+        assert_eq!(vec.row(2), &[8, 9, 101, 12]);
+        assert_eq!(vec.column(3).copied().collect::<Vec<usize>>(), vec![31, 8, 12]);
+
+        vec.view_mut(((0, 0), (2, 2))).fill(0);
+        assert_eq!(vec.values().filter(|v| **v == 0).count(), 4);
+
+        // Caixas 3x3 do sudoku
+        let mut sudoku = Vec2D::new(9, 9, 0);
+        sudoku[(4, 4)] = 5;
+        let boxes: Vec<usize> = (0..9).map(|b| sudoku.view((((b % 3) * 3, (b / 3) * 3), (3, 3))).values().sum()).collect();
+        assert_eq!(boxes, vec![0, 0, 0, 0, 5, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Retângulo fora dos limites")]
+    pub fn vec2d_view_overflow() {
+        // y + h estoura o usize, deve ser o mesmo panic de fora dos limites
+        let vec = Vec2D::new(2, 2, 0);
+        vec.view(((1, usize::MAX), (1, 2)));
+    }
+}
@@ -1,17 +1,142 @@
-use std::{mem, ops::{Index, IndexMut}};
+use std::{error::Error, fmt, iter::Enumerate, mem, ops::{Index, IndexMut}, slice, vec};
 
 use rand::distr::slice::Empty;
 
 // valor de índice que será utilizado para representar a ausência de conexão
 pub const NULL_INDEX: usize = usize::MAX;
 
+/**
+ * Tipo de índice utilizado nas ligações (pilha de vazios do pool, next/prev da LinkedList, ...)
+ * 
+ * Com u16 ou u32 as ligações ocupam menos memória, mas o pool fica limitado a NULL espaços
+ * (o maior valor do tipo é reservado para representar a ausência de conexão)
+ */
+pub trait PoolIndex: Copy + Eq + fmt::Debug {
+    const NULL: Self;
+
+    /** None se o índice não cabe no tipo (ou se seria o próprio NULL) */
+    fn from_usize(index: usize) -> Option<Self>;
+
+    fn to_usize(self) -> usize;
+}
+
+impl PoolIndex for usize {
+    const NULL: Self = NULL_INDEX;
+
+    fn from_usize(index: usize) -> Option<Self> { Some(index) }
+
+    fn to_usize(self) -> usize { self }
+}
+
+impl PoolIndex for u32 {
+    const NULL: Self = u32::MAX;
+
+    fn from_usize(index: usize) -> Option<Self> {
+        match u32::try_from(index) {
+            Ok(index) if index != Self::NULL => Some(index),
+            _ => None
+        }
+    }
+
+    fn to_usize(self) -> usize { self as usize }
+}
+
+impl PoolIndex for u16 {
+    const NULL: Self = u16::MAX;
+
+    fn from_usize(index: usize) -> Option<Self> {
+        match u16::try_from(index) {
+            Ok(index) if index != Self::NULL => Some(index),
+            _ => None
+        }
+    }
+
+    fn to_usize(self) -> usize { self as usize }
+}
+
+/**
+ * Erro ao alocar em um pool que já está com todos os índices possíveis ocupados (ex: 65535 nós com u16)
+ * Devolve o valor que não foi possível inserir
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapacityOverflowError<T> {
+    pub value: T
+}
+
+impl<T> fmt::Display for CapacityOverflowError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "capacidade máxima do tipo de índice foi atingida")
+    }
+}
+
+impl<T: fmt::Debug> Error for CapacityOverflowError<T> {}
+
 #[derive(Debug, Clone)]
-enum IndexNode<T> {
+pub(super) enum IndexNode<T, I = usize> {
     Filled(T),
-    Empty(usize)
+    Empty(I)
+}
+
+/**
+ * Cada espaço do pool guarda a geração atual junto do nó
+ * A geração é incrementada toda vez que o espaço é liberado, assim um handle antigo deixa de ser válido
+ */
+#[derive(Debug, Clone)]
+pub(super) struct PoolSlot<T, I = usize> {
+    pub(super) generation: u32,
+    pub(super) node: IndexNode<T, I>
 }
 
-impl<T> IndexNode<T> {
+/**
+ * Handle retornado por alloc_node: índice + geração
+ * 
+ * Resolve o problema ABA: depois de free_node o mesmo espaço é reaproveitado por outro valor,
+ * mas a geração do espaço muda, então o handle antigo não acessa o novo ocupante.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolHandle {
+    index: usize,
+    generation: u32
+}
+
+impl PoolHandle {
+    pub(super) fn new(index: usize, generation: u32) -> PoolHandle {
+        PoolHandle { index: index, generation: generation }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/**
+ * Forma de endereçar um nó do pool
+ * - usize: índice 'cru', não valida a geração (para estruturas como LinkedList que controlam os próprios índices)
+ * - PoolHandle: valida a geração, retorna None/panic se o handle é de um valor que já foi liberado
+ */
+pub trait PoolKey: Copy {
+    fn index(&self) -> usize;
+
+    fn matches(&self, generation: u32) -> bool;
+}
+
+impl PoolKey for usize {
+    fn index(&self) -> usize { *self }
+
+    fn matches(&self, _generation: u32) -> bool { true }
+}
+
+impl PoolKey for PoolHandle {
+    fn index(&self) -> usize { self.index }
+
+    fn matches(&self, generation: u32) -> bool { self.generation == generation }
+}
+
+impl<T, I> IndexNode<T, I> {
     /** Não poderá usar o valor depois de chamar, retorna o ownership */
     fn get_owned(self) -> Option<T> {
         match self {
@@ -43,6 +168,14 @@ impl<T> IndexNode<T> {
  * - Cada valor vazio contém o next do próximo valor vazio, ou NULL
  * - Ao criar novo nó é realizado 'push' nesta pilha ligada, e ao remover é feito 'pop'
  * 
+ * Para evitar que um índice antigo acesse o novo ocupante de um espaço reaproveitado, cada espaço possui uma geração
+ * - alloc_node retorna um PoolHandle (índice + geração)
+ * - get_node, get_mut_node, free_node e pool[handle] só funcionam se a geração do handle for a mesma do espaço
+ * - Ainda é possível acessar com um usize (sem validar a geração), usando handle.index()
+ * 
+ * O tipo I é o índice utilizado na pilha de vazios (padrão usize), com VecPool<T, u16> cada espaço vazio
+ * ocupa menos memória, mas o pool fica limitado a u16::MAX espaços (try_alloc_node retorna erro)
+ * 
  * Referências/Trabalho similar:
  * - Slotmap (https://github.com/orlp/slotmap)
  *   - https://www.reddit.com/r/rust/comments/8zkedd/slotmap_a_new_crate_for_storing_values_with/
@@ -51,47 +184,112 @@ impl<T> IndexNode<T> {
  * - Slab (https://github.com/tokio-rs/slab)
  * - https://www.reddit.com/r/rust/comments/gfo1uw/benchmarking_slotmap_slab_stable_vec_etc/
  */
-pub struct VecPool<T> {
-    arr: Vec<IndexNode<T>>,
+pub struct VecPool<T, I = usize> {
+    arr: Vec<PoolSlot<T, I>>,
     length: usize,
-    last_empty: usize
+    last_empty: I,
+    // geração inicial dos espaços novos, após clear() continua maior que todas as anteriores
+    first_generation: u32
 }
 
 impl<T> VecPool<T> {
     pub fn new() -> VecPool<T> {
+        Self::new_indexed()
+    }
+
+    pub fn with_capacity(capacity: usize) -> VecPool<T> {
+        Self::with_capacity_indexed(capacity)
+    }
+}
+
+impl<T, I: PoolIndex> VecPool<T, I> {
+    /**
+     * Mesmo que new(), mas com outro tipo de índice: VecPool::<T, u16>::new_indexed()
+     * (new() é somente para usize, para não precisar informar o tipo de índice sempre)
+     */
+    pub fn new_indexed() -> VecPool<T, I> {
         VecPool { 
             arr: Vec::new(),
             length: 0,
-            last_empty: NULL_INDEX
+            last_empty: I::NULL,
+            first_generation: 0
+        }
+    }
+
+    pub fn with_capacity_indexed(capacity: usize) -> VecPool<T, I> {
+        VecPool { 
+            arr: Vec::with_capacity(capacity),
+            length: 0,
+            last_empty: I::NULL,
+            first_generation: 0
+        }
+    }
+
+    /**
+     * Quantidade máxima de espaços que o tipo de índice consegue endereçar
+     */
+    pub fn max_capacity(&self) -> usize {
+        I::NULL.to_usize()
+    }
+
+    /**
+     * Quantos espaços cabem sem precisar realocar o Vec (contando os espaços vazios)
+     */
+    pub fn capacity(&self) -> usize {
+        self.arr.capacity()
+    }
+
+    /**
+     * Reserva espaço para pelo menos mais 'additional' nós além dos que já estão ocupados
+     */
+    pub fn reserve(&mut self, additional: usize) {
+        // Os espaços vazios já existentes podem ser reaproveitados
+        let free = self.arr.len() - self.length;
+        if additional > free {
+            self.arr.reserve(additional - free);
         }
     }
 
     /**
      * Aloca um novo nó na lista, aproveitando espaços vazios se possível
      * 
-     * Retorna o índice que foi criado
+     * Retorna o handle (índice + geração) que foi criado
+     * Panic se o tipo de índice não comporta mais espaços, ver try_alloc_node
+     */
+    pub fn alloc_node(&mut self, value: T) -> PoolHandle {
+        match self.try_alloc_node(value) {
+            Ok(handle) => handle,
+            Err(_) => panic!("VecPool: capacidade máxima do índice atingida ({})", self.max_capacity()),
+        }
+    }
+
+    /**
+     * Mesmo que alloc_node, mas retorna erro (devolvendo o valor) se não cabe mais nenhum espaço no tipo de índice
      */
-    pub fn alloc_node(&mut self, value: T) -> usize {
-        let node = IndexNode::Filled(value);
-        if self.last_empty == NULL_INDEX { // não tem nenhum vazio, adicionar mais um no final do array
-            self.arr.push(node);
+    pub fn try_alloc_node(&mut self, value: T) -> Result<PoolHandle, CapacityOverflowError<T>> {
+        if self.last_empty == I::NULL { // não tem nenhum vazio, adicionar mais um no final do array
+            if I::from_usize(self.arr.len()).is_none() {
+                return Err(CapacityOverflowError { value: value });
+            }
+
+            self.arr.push(PoolSlot { generation: self.first_generation, node: IndexNode::Filled(value) });
             self.length += 1;
 
-            return self.arr.len()-1;
+            return Ok(PoolHandle { index: self.arr.len()-1, generation: self.first_generation });
         } else {
             // fazer pop() da pilha de valores vazios
             // 1 - obter índice do espaço vazio
-            let free_node_index = self.last_empty;
+            let free_node_index = self.last_empty.to_usize();
 
-            let free_node = &mut self.arr[free_node_index];
-            if let &mut IndexNode::Empty(next) = free_node {
+            let free_slot = &mut self.arr[free_node_index];
+            if let IndexNode::Empty(next) = free_slot.node {
                 self.last_empty = next;
 
-                // Re-Inicializar os valores
-                *free_node = node;
+                // Re-Inicializar os valores (a geração já foi incrementada no free_node)
+                free_slot.node = IndexNode::Filled(value);
                 self.length += 1;
 
-                return free_node_index;
+                return Ok(PoolHandle { index: free_node_index, generation: free_slot.generation });
             } else {
                 panic!("NUNCA DEVERIA OCORRER: Ao obter o last_empty obteve um Filled");
             }
@@ -101,17 +299,21 @@ impl<T> VecPool<T> {
     /**
      * Libera um nó da lista tornando um espaço vazio disponível, e retorna o valor que estava nele,
      */
-    pub fn free_node(&mut self, node: usize) -> Option<T> {
-        let len = self.arr.len();
-        if let Some(arr_node) = self.arr.get_mut(node) {
-            if let IndexNode::Empty(_) = arr_node {
+    pub fn free_node<K: PoolKey>(&mut self, node: K) -> Option<T> {
+        let index = node.index();
+        if let Some(slot) = self.arr.get_mut(index) {
+            if let IndexNode::Empty(_) = slot.node {
                 return None; // free em nó Empty (double free?)
-            }            
+            }
+            if !node.matches(slot.generation) {
+                return None; // free com handle de uma geração anterior
+            }
 
             // Se removeu e não é no fim do array, marca como vazio
             // - fazer push() na pilha de valores vazios
-            let ret = mem::replace(arr_node, IndexNode::Empty(self.last_empty));
-            self.last_empty = node;
+            let ret = mem::replace(&mut slot.node, IndexNode::Empty(self.last_empty));
+            slot.generation = slot.generation.wrapping_add(1);
+            self.last_empty = I::from_usize(index).unwrap();
             self.length -= 1;
 
             ret.get_owned()
@@ -121,66 +323,361 @@ impl<T> VecPool<T> {
     }
 
     pub fn clear(&mut self) {
+        self.bump_first_generation();
+
         self.arr.clear();
         self.length = 0;
-        self.last_empty = NULL_INDEX;
+        self.last_empty = I::NULL;
+    }
+
+    /**
+     * Os espaços criados depois de um clear devem ter geração maior que qualquer handle já retornado
+     */
+    fn bump_first_generation(&mut self) {
+        self.bump_first_generation_from(0);
+    }
+
+    /**
+     * Mesmo que bump_first_generation, mas somente para os espaços que serão removidos do final (a partir de start)
+     */
+    fn bump_first_generation_from(&mut self, start: usize) {
+        for slot in self.arr[start..].iter() {
+            let next_generation = slot.generation.wrapping_add(1);
+            if next_generation > self.first_generation {
+                self.first_generation = next_generation;
+            }
+        }
+    }
+
+    /**
+     * Remove os espaços vazios do final do array e libera a memória que sobrar
+     * 
+     * Os espaços vazios no meio continuam, e a pilha de vazios é reconstruída sem os que foram removidos.
+     * Não altera o índice de nenhum valor ocupado.
+     */
+    pub fn shrink_to_fit(&mut self) {
+        let mut new_len = self.arr.len();
+        while new_len > 0 {
+            if let IndexNode::Filled(_) = self.arr[new_len-1].node {
+                break;
+            }
+            new_len -= 1;
+        }
+        self.bump_first_generation_from(new_len);
+        self.arr.truncate(new_len);
+        self.arr.shrink_to_fit();
+
+        // Reconstruir a pilha de vazios, pois alguns dos vazios removidos podiam estar no meio dela
+        self.last_empty = I::NULL;
+        for index in 0..self.arr.len() {
+            if let IndexNode::Empty(_) = self.arr[index].node {
+                self.arr[index].node = IndexNode::Empty(self.last_empty);
+                self.last_empty = I::from_usize(index).unwrap();
+            }
+        }
+    }
+
+    /**
+     * Move todos os valores ocupados para o início do array, removendo todos os espaços vazios
+     * 
+     * Retorna o mapeamento de índices antigo -> novo (NULL_INDEX para os que estavam vazios),
+     * para que quem guarda índices (LinkedList, GraphPool, ...) possa reescrever suas ligações.
+     * Os handles antigos dos valores que foram movidos deixam de ser válidos.
+     */
+    pub fn compact(&mut self) -> Vec<usize> {
+        let mut remap = vec![NULL_INDEX; self.arr.len()];
+        let mut write = 0;
+        for (read, new_index) in remap.iter_mut().enumerate() {
+            if let IndexNode::Empty(_) = self.arr[read].node {
+                continue;
+            }
+
+            if read != write {
+                // Todos antes de write estão ocupados, então write é um espaço vazio
+                // read é liberado (incrementando a geração), e write mantêm a geração de quando foi liberado
+                let node = mem::replace(&mut self.arr[read].node, IndexNode::Empty(I::NULL));
+                self.arr[read].generation = self.arr[read].generation.wrapping_add(1);
+                self.arr[write].node = node;
+            }
+            *new_index = write;
+            write += 1;
+        }
+
+        self.bump_first_generation_from(write);
+        self.arr.truncate(write);
+        self.last_empty = I::NULL;
+
+        return remap;
+    }
+
+    /**
+     * Atravessa os valores ocupados (pulando os espaços vazios), junto do handle de cada um
+     */
+    pub fn iter(&self) -> VecPoolIter<'_, T, I> {
+        VecPoolIter { 
+            inner: self.arr.iter().enumerate(), 
+            remaining: self.length 
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> VecPoolIterMut<'_, T, I> {
+        VecPoolIterMut { 
+            inner: self.arr.iter_mut().enumerate(), 
+            remaining: self.length 
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = PoolHandle> + '_ {
+        self.iter().map(|(handle, _)| handle)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /**
+     * Remove todos os valores, retornando eles junto do handle que tinham
+     * Mesmo que o iterador não seja consumido até o fim, o pool fica vazio
+     */
+    pub fn drain(&mut self) -> VecPoolDrain<'_, T, I> {
+        self.bump_first_generation();
+
+        let remaining = self.length;
+        self.length = 0;
+        self.last_empty = I::NULL;
+
+        VecPoolDrain { 
+            inner: self.arr.drain(..).enumerate(), 
+            remaining: remaining 
+        }
+    }
+
+    /**
+     * Mantêm somente os valores em que f retornar true, os outros são liberados
+     */
+    pub fn retain<F>(&mut self, mut f: F) 
+    where 
+        F: FnMut(PoolHandle, &mut T) -> bool
+    {
+        for index in 0..self.arr.len() {
+            let slot = &mut self.arr[index];
+            let handle = PoolHandle { index: index, generation: slot.generation };
+            let keep = match &mut slot.node {
+                IndexNode::Filled(value) => f(handle, value),
+                IndexNode::Empty(_) => true,
+            };
+            if !keep {
+                self.free_node(handle);
+            }
+        }
     }
 
     pub fn len(&self) -> usize {
         self.length
     }
 
-    pub fn get_node(&self, node: usize) -> Option<&T> {
-        match self.arr.get(node) {
-            Some(node) => { node.try_get() },
-            None => { None },
+    /**
+     * Tamanho do array interno, contando os espaços vazios
+     */
+    pub fn arr_len(&self) -> usize {
+        self.arr.len()
+    }
+
+    /**
+     * Verifica se o handle (ou índice) ainda aponta para um valor ocupado
+     */
+    pub fn contains<K: PoolKey>(&self, node: K) -> bool {
+        self.get_node(node).is_some()
+    }
+
+    pub fn get_node<K: PoolKey>(&self, node: K) -> Option<&T> {
+        match self.arr.get(node.index()) {
+            Some(slot) if node.matches(slot.generation) => { slot.node.try_get() },
+            _ => { None },
         }
     }
 
-    pub fn get_mut_node(&mut self, node: usize) -> Option<&mut T> {
-        match self.arr.get_mut(node) {
-            Some(node) => { node.try_get_mut() },
-            None => { None },
+    pub fn get_mut_node<K: PoolKey>(&mut self, node: K) -> Option<&mut T> {
+        match self.arr.get_mut(node.index()) {
+            Some(slot) if node.matches(slot.generation) => { slot.node.try_get_mut() },
+            _ => { None },
         }
     }
 }
 
 
 // let value = pool[index];
-impl<T> Index<usize> for VecPool<T> {
+impl<T, I: PoolIndex, K: PoolKey> Index<K> for VecPool<T, I> {
     type Output = T;
 
-    fn index(&self, node: usize) -> &Self::Output {
-        match &self.arr[node] {
+    fn index(&self, node: K) -> &Self::Output {
+        let slot = &self.arr[node.index()];
+        if !node.matches(slot.generation) {
+            panic!("Acessou com handle de geração antiga {:?}", node.index());
+        }
+        match &slot.node {
             IndexNode::Filled(value) => value,
-            IndexNode::Empty(_) => panic!("Acessou um Empty {:?}", node),
+            IndexNode::Empty(_) => panic!("Acessou um Empty {:?}", node.index()),
         }
     }
 }
 
 // pool[index] = value;
-impl<T> IndexMut<usize> for VecPool<T> {
-    fn index_mut(&mut self, node: usize) -> &mut Self::Output {
-        match &mut self.arr[node] {
+impl<T, I: PoolIndex, K: PoolKey> IndexMut<K> for VecPool<T, I> {
+    fn index_mut(&mut self, node: K) -> &mut Self::Output {
+        let slot = &mut self.arr[node.index()];
+        if !node.matches(slot.generation) {
+            panic!("Acessou com handle de geração antiga {:?}", node.index());
+        }
+        match &mut slot.node {
             IndexNode::Filled(value) => value,
-            IndexNode::Empty(_) => panic!("Acessou um Empty {:?}", node),            
+            IndexNode::Empty(_) => panic!("Acessou um Empty {:?}", node.index()),            
         }
     }
 }
 
-impl<T: Clone> Clone for VecPool<T> {
+impl<T: Clone, I: PoolIndex> Clone for VecPool<T, I> {
     fn clone(&self) -> Self {
         VecPool { 
             arr: self.arr.clone(), 
             length: self.length, 
-            last_empty: self.last_empty 
+            last_empty: self.last_empty,
+            first_generation: self.first_generation
+        }
+    }
+}
+
+// 'a lifetime, o iterador deve viver tanto quanto o pool
+pub struct VecPoolIter<'a, T, I = usize> {
+    inner: Enumerate<slice::Iter<'a, PoolSlot<T, I>>>,
+    remaining: usize
+}
+
+impl<'a, T, I> Iterator for VecPoolIter<'a, T, I> {
+    type Item = (PoolHandle, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.inner.by_ref() {
+            if let IndexNode::Filled(value) = &slot.node {
+                self.remaining -= 1;
+                return Some((PoolHandle { index: index, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+pub struct VecPoolIterMut<'a, T, I = usize> {
+    inner: Enumerate<slice::IterMut<'a, PoolSlot<T, I>>>,
+    remaining: usize
+}
+
+impl<'a, T, I> Iterator for VecPoolIterMut<'a, T, I> {
+    type Item = (PoolHandle, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.inner.by_ref() {
+            if let IndexNode::Filled(value) = &mut slot.node {
+                self.remaining -= 1;
+                return Some((PoolHandle { index: index, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+pub struct VecPoolIntoIter<T, I = usize> {
+    inner: Enumerate<vec::IntoIter<PoolSlot<T, I>>>,
+    remaining: usize
+}
+
+impl<T, I> Iterator for VecPoolIntoIter<T, I> {
+    type Item = (PoolHandle, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.inner.by_ref() {
+            if let IndexNode::Filled(value) = slot.node {
+                self.remaining -= 1;
+                return Some((PoolHandle { index: index, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+pub struct VecPoolDrain<'a, T, I = usize> {
+    inner: Enumerate<vec::Drain<'a, PoolSlot<T, I>>>,
+    remaining: usize
+}
+
+impl<T, I> Iterator for VecPoolDrain<'_, T, I> {
+    type Item = (PoolHandle, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.inner.by_ref() {
+            if let IndexNode::Filled(value) = slot.node {
+                self.remaining -= 1;
+                return Some((PoolHandle { index: index, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, I> ExactSizeIterator for VecPoolIter<'_, T, I> {}
+impl<T, I> ExactSizeIterator for VecPoolIterMut<'_, T, I> {}
+impl<T, I> ExactSizeIterator for VecPoolIntoIter<T, I> {}
+impl<T, I> ExactSizeIterator for VecPoolDrain<'_, T, I> {}
+
+// for (handle, value) in &pool
+impl<'a, T, I: PoolIndex> IntoIterator for &'a VecPool<T, I> {
+    type Item = (PoolHandle, &'a T);
+    type IntoIter = VecPoolIter<'a, T, I>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// for (handle, value) in &mut pool
+impl<'a, T, I: PoolIndex> IntoIterator for &'a mut VecPool<T, I> {
+    type Item = (PoolHandle, &'a mut T);
+    type IntoIter = VecPoolIterMut<'a, T, I>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// for (handle, value) in pool
+impl<T, I: PoolIndex> IntoIterator for VecPool<T, I> {
+    type Item = (PoolHandle, T);
+    type IntoIter = VecPoolIntoIter<T, I>;
+    fn into_iter(self) -> Self::IntoIter {
+        VecPoolIntoIter { 
+            inner: self.arr.into_iter().enumerate(), 
+            remaining: self.length 
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::VecPool;
+    use super::{PoolIndex, VecPool, NULL_INDEX};
     use std::fmt::Write;
 
     #[test]
@@ -238,5 +735,241 @@ mod test {
         let mut f = String::new();
         write!(f,"{} {}", pool[nome], pool[sobrenome]);
         assert_eq!(f, "João Silva");
+
+        // Também pode acessar pelo índice 'cru'
+        assert_eq!(pool[nome.index()], "João");
+    }
+
+    #[test]
+    pub fn generation_handles() {
+        let mut pool = VecPool::new();
+        let a = pool.alloc_node('A');
+        assert_eq!(pool.free_node(a), Some('A'));
+
+        // Reaproveita o mesmo espaço, mas com outra geração
+        let b = pool.alloc_node('B');
+        assert_eq!(a.index(), b.index());
+        assert_ne!(a.generation(), b.generation());
+
+        // O handle antigo não acessa o novo ocupante
+        assert_eq!(pool.get_node(a), None);
+        assert_eq!(pool.get_mut_node(a), None);
+        assert_eq!(pool.free_node(a), None);
+        assert!(!pool.contains(a));
+        assert_eq!(pool.get_node(b), Some(&'B'));
+        assert_eq!(pool.len(), 1);
+
+        // Depois do clear os handles antigos continuam inválidos
+        pool.clear();
+        let c = pool.alloc_node('C');
+        assert_eq!(c.index(), b.index());
+        assert_eq!(pool.get_node(a), None);
+        assert_eq!(pool.get_node(b), None);
+        assert_eq!(pool.get_node(c), Some(&'C'));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn index_stale_handle() {
+        let mut pool = VecPool::new();
+        let a = pool.alloc_node('A');
+        pool.free_node(a);
+        pool.alloc_node('B');
+
+        let _ = pool[a];
+    }
+
+    #[test]
+    pub fn iter_skip_empty() {
+        let mut pool = VecPool::new();
+        let a = pool.alloc_node('A');
+        let b = pool.alloc_node('B');
+        let c = pool.alloc_node('C');
+        let d = pool.alloc_node('D');
+        pool.free_node(b);
+
+        let values: String = pool.values().collect();
+        assert_eq!(values, "ACD");
+        assert_eq!(pool.iter().len(), 3);
+
+        let keys: Vec<_> = pool.keys().collect();
+        assert_eq!(keys, vec![a, c, d]);
+
+        for (_, value) in pool.iter_mut() {
+            *value = value.to_ascii_lowercase();
+        }
+        for value in pool.values_mut() {
+            value.make_ascii_uppercase();
+        }
+        for (handle, value) in &mut pool {
+            if handle == d { *value = '?'; }
+        }
+        let mut values = String::new();
+        for (_, value) in &pool {
+            values.push(*value);
+        }
+        assert_eq!(values, "AC?");
+
+        let owned: Vec<_> = pool.into_iter().collect();
+        assert_eq!(owned, vec![(a, 'A'), (c, 'C'), (d, '?')]);
+    }
+
+    #[test]
+    pub fn drain_and_retain() {
+        let mut pool = VecPool::new();
+        let handles: Vec<_> = (0..10).map(|i| pool.alloc_node(i)).collect();
+
+        // Remove os ímpares
+        pool.retain(|_, value| *value % 2 == 0);
+        assert_eq!(pool.len(), 5);
+        assert_eq!(pool.get_node(handles[1]), None);
+        assert_eq!(pool.get_node(handles[2]), Some(&2));
+        assert_eq!(pool.values().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+
+        // Os espaços liberados pelo retain são reaproveitados (o último liberado é o topo da pilha de vazios)
+        let cem = pool.alloc_node(100);
+        assert_eq!(cem.index(), handles[9].index());
+        assert_eq!(pool.arr.len(), 10);
+
+        let drained: Vec<_> = pool.drain().map(|(_, value)| value).collect();
+        assert_eq!(drained, vec![0, 2, 4, 6, 8, 100]);
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.iter().next(), None);
+
+        // Handles de antes do drain continuam inválidos
+        let novo = pool.alloc_node(42);
+        assert_eq!(novo.index(), handles[0].index());
+        assert_eq!(pool.get_node(handles[0]), None);
+        assert_eq!(pool.get_node(novo), Some(&42));
+    }
+
+    #[test]
+    pub fn capacity() {
+        let mut pool: VecPool<i32> = VecPool::with_capacity(10);
+        assert!(pool.capacity() >= 10);
+        assert_eq!(pool.len(), 0);
+
+        let handles: Vec<_> = (0..10).map(|i| pool.alloc_node(i)).collect();
+        pool.free_node(handles[3]);
+        pool.free_node(handles[4]);
+
+        // Os 2 espaços vazios já bastam, não precisa crescer
+        let cap = pool.capacity();
+        pool.reserve(2);
+        assert_eq!(pool.capacity(), cap);
+
+        pool.reserve(20);
+        assert!(pool.capacity() >= 28);
+    }
+
+    #[test]
+    pub fn shrink_to_fit() {
+        let mut pool = VecPool::new();
+        let handles: Vec<_> = (0..10).map(|i| pool.alloc_node(i)).collect();
+        for i in [2, 7, 8, 9] {
+            pool.free_node(handles[i]);
+        }
+        assert_eq!(pool.arr.len(), 10);
+
+        pool.shrink_to_fit();
+        assert_eq!(pool.arr.len(), 7);
+        assert_eq!(pool.len(), 6);
+        // Índices dos ocupados não mudam
+        for i in [0, 1, 3, 4, 5, 6] {
+            assert_eq!(pool.get_node(handles[i]), Some(&(i as i32)));
+        }
+
+        // O único vazio que sobrou é reaproveitado, depois volta a crescer no final
+        let a = pool.alloc_node(100);
+        assert_eq!(a.index(), 2);
+        let b = pool.alloc_node(200);
+        assert_eq!(b.index(), 7);
+        assert_eq!(pool.get_node(handles[7]), None);
+
+        // Tudo vazio
+        pool.clear();
+        pool.alloc_node(1);
+        pool.free_node(0);
+        pool.shrink_to_fit();
+        assert_eq!(pool.arr.len(), 0);
+        assert_eq!(pool.alloc_node(2).index(), 0);
+    }
+
+    #[test]
+    pub fn compact() {
+        let mut pool = VecPool::new();
+        let letras = ['A','B','C','D','E','F','G'];
+        let handles: Vec<_> = letras.iter().map(|c| pool.alloc_node(*c)).collect();
+        for i in [0, 2, 3, 6] {
+            pool.free_node(handles[i]);
+        }
+
+        let remap = pool.compact();
+        assert_eq!(remap, vec![NULL_INDEX, 0, NULL_INDEX, NULL_INDEX, 1, 2, NULL_INDEX]);
+        assert_eq!(pool.arr.len(), 3);
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.values().collect::<String>(), "BEF");
+        for (old, &new) in remap.iter().enumerate() {
+            if new != NULL_INDEX {
+                assert_eq!(pool.get_node(new), Some(&letras[old]));
+            }
+        }
+
+        // Handles antigos de valores movidos não são mais válidos (B foi de 1 para 0 e E de 4 para 1)
+        assert_eq!(pool.get_node(handles[1]), None);
+        assert_eq!(pool.get_node(handles[4]), None);
+        let keys: Vec<_> = pool.keys().collect();
+        assert_eq!(pool[keys[1]], 'E');
+
+        // Depois de compactar não tem vazios, o próximo vai no final
+        let h = pool.alloc_node('H');
+        assert_eq!(h.index(), 3);
+        assert_eq!(pool.get_node(handles[3]), None);
+    }
+
+    #[test]
+    pub fn compact_index() {
+        let mut pool: VecPool<u8, u16> = VecPool::new_indexed();
+        assert_eq!(pool.max_capacity(), u16::MAX as usize);
+
+        for i in 0..pool.max_capacity() {
+            assert!(pool.try_alloc_node((i % 256) as u8).is_ok());
+        }
+        assert_eq!(pool.len(), 65535);
+
+        // Não cabe mais nenhum, devolve o valor
+        let err = pool.try_alloc_node(42).unwrap_err();
+        assert_eq!(err.value, 42);
+        assert_eq!(pool.len(), 65535);
+
+        // Liberando um espaço volta a caber
+        assert_eq!(pool.free_node(300), Some(44));
+        let h = pool.try_alloc_node(7).unwrap();
+        assert_eq!(h.index(), 300);
+        assert_eq!(pool[300], 7);
+        assert!(pool.try_alloc_node(8).is_err());
+
+        let mut small: VecPool<char, u32> = VecPool::with_capacity_indexed(4);
+        let a = small.alloc_node('A');
+        let b = small.alloc_node('B');
+        small.free_node(a);
+        small.free_node(b);
+        assert_eq!(small.alloc_node('C').index(), b.index());
+        assert_eq!(small.alloc_node('D').index(), a.index());
+        assert_eq!(small.get_node(a), None);
+        assert_eq!(small.values().collect::<String>(), "DC");
+
+        assert_eq!(u16::from_usize(65535), None);
+        assert_eq!(u16::from_usize(70000), None);
+        assert_eq!(u16::from_usize(65534), Some(65534));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn compact_index_overflow_panic() {
+        let mut pool: VecPool<(), u16> = VecPool::new_indexed();
+        for _ in 0..=u16::MAX {
+            pool.alloc_node(());
+        }
     }
 }
\ No newline at end of file
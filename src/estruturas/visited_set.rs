@@ -0,0 +1,108 @@
+use std::{collections::HashSet, hash::Hash};
+
+use super::Vec2D;
+
+/**
+ * Lista de vértices já visitados, usada pelo GraphIter
+ *
+ * - HashSet<T>: padrão, funciona com qualquer vértice, mas calcula o hash a cada consulta
+ * - Vec2D<bool>: para grades, cada posição é um bool no Vec2D (sem hash, só o cálculo do índice).
+ *   Precisa ter o tamanho da grade, posições fora dela contam como já visitadas (nunca são exploradas)
+ */
+pub trait VisitedSet<T> {
+    /**
+     * Marca como visitado, retorna false se já estava visitado
+     */
+    fn insert(&mut self, node: T) -> bool;
+
+    fn contains(&self, node: &T) -> bool;
+
+    /**
+     * Desmarca todos, sem liberar a memória
+     */
+    fn clear(&mut self);
+}
+
+impl<T: Eq + Hash> VisitedSet<T> for HashSet<T> {
+    fn insert(&mut self, node: T) -> bool {
+        HashSet::insert(self, node)
+    }
+
+    fn contains(&self, node: &T) -> bool {
+        HashSet::contains(self, node)
+    }
+
+    fn clear(&mut self) {
+        HashSet::clear(self);
+    }
+}
+
+impl VisitedSet<(usize, usize)> for Vec2D<bool> {
+    fn insert(&mut self, (x, y): (usize, usize)) -> bool {
+        let (w, h) = self.size();
+        if x >= w || y >= h || self[(x, y)] {
+            return false;
+        }
+        self[(x, y)] = true;
+        return true;
+    }
+
+    fn contains(&self, &(x, y): &(usize, usize)) -> bool {
+        let (w, h) = self.size();
+        return x >= w || y >= h || self[(x, y)];
+    }
+
+    fn clear(&mut self) {
+        Vec2D::clear(self, false);
+    }
+}
+
+impl VisitedSet<(i32, i32)> for Vec2D<bool> {
+    fn insert(&mut self, (x, y): (i32, i32)) -> bool {
+        if x < 0 || y < 0 {
+            return false;
+        }
+        return VisitedSet::<(usize, usize)>::insert(self, (x as usize, y as usize));
+    }
+
+    fn contains(&self, &(x, y): &(i32, i32)) -> bool {
+        return x < 0 || y < 0 || VisitedSet::<(usize, usize)>::contains(self, &(x as usize, y as usize));
+    }
+
+    fn clear(&mut self) {
+        Vec2D::clear(self, false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::estruturas::Vec2D;
+
+    use super::VisitedSet;
+
+    #[test]
+    pub fn visited_set() {
+        let mut hash: HashSet<(i32, i32)> = HashSet::new();
+        let mut grid = Vec2D::new(3, 2, false);
+        for set in [&mut hash as &mut dyn VisitedSet<(i32, i32)>, &mut grid] {
+            assert!(set.insert((1, 1)));
+            assert!(!set.insert((1, 1)));
+            assert!(set.contains(&(1, 1)));
+            assert!(!set.contains(&(2, 1)));
+            set.clear();
+            assert!(!set.contains(&(1, 1)));
+            assert!(set.insert((1, 1)));
+        }
+
+        // Fora da grade nunca é visitado
+        assert!(!grid.insert((-1, 0)));
+        assert!(!grid.insert((3, 0)));
+        assert!(grid.contains(&(0, 2)));
+        assert!(!VisitedSet::<(usize, usize)>::insert(&mut grid, (0, 5)));
+        assert!(VisitedSet::<(usize, usize)>::insert(&mut grid, (2, 0)));
+        assert!(grid[(2, 0)]);
+        assert_eq!(grid.values().filter(|v| **v).count(), 2);
+    }
+}
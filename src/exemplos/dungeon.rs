@@ -3,7 +3,7 @@ use std::{cmp::{max, min}, io::{self, Stdout}};
 use crossterm::{cursor::*, event::KeyCode, style::*, terminal::*, ExecutableCommand, QueueableCommand};
 use rand::{rngs::{StdRng, ThreadRng}, seq::SliceRandom, Rng, SeedableRng};
 
-use crate::{estruturas::{Dir, GraphIter, GraphIterState, GraphSearch, Stack, Vec2D}, utils::{StyledChar, Terminal, TerminalHandler, TerminalScreen}};
+use crate::{estruturas::{Dir, EdgePolicy, GraphIter, GraphIterState, GraphSearch, Stack, Vec2D}, utils::{StyledChar, Terminal, TerminalHandler, TerminalScreen}};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -104,6 +104,8 @@ struct DungeonGame {
     screen: TerminalScreen,
     tiles: Vec2D<MapTile>,
     pos: (i32, i32),
+    // Reused by update_visible every move
+    visible_iter: GraphIter<(i32, i32), usize, Vec2D<bool>>,
 
     seed: u64,
     level: i32,
@@ -116,6 +118,7 @@ impl DungeonGame {
             screen: TerminalScreen::new(size),
             tiles: Vec2D::new(size.0/2, size.1, MapTile::new(Tile::Void)),
             pos: (0,0),
+            visible_iter: GraphIter::breadth_first((0,0)).with_visited(Vec2D::new(size.0/2, size.1, false)),
             seed: 0xcafe,
             level: -1,
             next_level: 0
@@ -236,10 +239,12 @@ impl DungeonGame {
 
             let (x,y) = (pos.0 as i32, pos.1 as i32);
             let gnd = MapTile::new(Tile::Ground);
-            let n = self.tiles.get((x  , y-1)).unwrap_or(&gnd).tile != Tile::Ground;
-            let w = self.tiles.get((x-1, y  )).unwrap_or(&gnd).tile != Tile::Ground;
-            let s = self.tiles.get((x  , y+1)).unwrap_or(&gnd).tile != Tile::Ground;
-            let e = self.tiles.get((x+1, y  )).unwrap_or(&gnd).tile != Tile::Ground;
+            // Ordem do neighbors4: cima, esquerda, direita, baixo
+            let mut walls = [false; 4];
+            for (wall, (_, neigh)) in walls.iter_mut().zip(self.tiles.neighbors4((x,y), EdgePolicy::Default(&gnd))) {
+                *wall = neigh.tile != Tile::Ground;
+            }
+            let [n, w, e, s] = walls;
 
             self.tiles[pos].tile = Tile::get_wall(n, w, s, e);
         }
@@ -316,12 +321,9 @@ impl DungeonGame {
 
     fn update_visible(&mut self) {
         // Mark surroundings as visible and explored
-        let (w,h) = self.tiles.size();
-        let (w,h) = (w as i32, h as i32);
-
         let (x,y) = self.pos;
-        let mut iter = GraphIter::<(i32, i32)>::breadth_first((x,y));
-        while let Some((mx,my)) = iter.next() {
+        self.visible_iter.reset((x,y));
+        while let Some((mx,my)) = self.visible_iter.next() {
             let thistile = &mut self.tiles[(mx as usize, my as usize)];
             thistile.mark_visible();
 
@@ -335,11 +337,8 @@ impl DungeonGame {
             }
             
             // Add neighbors
-            let mut neighs = [(mx+1,my),(mx,my-1),(mx-1,my),(mx,my+1), (mx+1,my+1),(mx-1,my-1),(mx-1,my+1),(mx+1,my-1)];
-            for neigh in neighs {
-                if neigh.0 >= 0 && neigh.0 < w && neigh.1 >= 0 && neigh.1 < h {
-                    iter.push_neighbor(neigh);
-                }
+            for (neigh, _) in self.tiles.neighbors8((mx,my), EdgePolicy::Clip) {
+                self.visible_iter.push_neighbor(neigh);
             }
         }
     }
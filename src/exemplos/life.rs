@@ -4,7 +4,7 @@ use std::mem;
 use crossterm::{cursor::*, event::KeyCode, style::*, terminal::*, ExecutableCommand, QueueableCommand};
 use rand::Rng;
 
-use crate::{estruturas::Vec2D, utils::{Terminal, TerminalHandler}};
+use crate::{estruturas::{EdgePolicy, Vec2D}, utils::{Terminal, TerminalHandler}};
 
 fn wrap_xy((x,y): (i32,i32), (w,h): (usize,usize)) -> (usize,usize) {
     (
@@ -59,19 +59,10 @@ impl Life {
      *  Any dead cell with exactly three live neighbours becomes a live cell, as if by reproduction.
      */
     pub fn process_cell(&self, pos: (usize,usize)) -> bool {
-        let mut vizinhos = 0;
-        let (x,y) = (pos.0 as i32, pos.1 as i32);
-        if self.grade[wrap_xy((x-1, y-1), self.size)] { vizinhos += 1; }
-        if self.grade[wrap_xy((x  , y-1), self.size)] { vizinhos += 1; }
-        if self.grade[wrap_xy((x+1, y-1), self.size)] { vizinhos += 1; }
-
-        if self.grade[wrap_xy((x-1, y  ), self.size)] { vizinhos += 1; }
-        // 0,0
-        if self.grade[wrap_xy((x+1, y  ), self.size)] { vizinhos += 1; }
-
-        if self.grade[wrap_xy((x-1, y+1), self.size)] { vizinhos += 1; }
-        if self.grade[wrap_xy((x  , y+1), self.size)] { vizinhos += 1; }
-        if self.grade[wrap_xy((x+1, y+1), self.size)] { vizinhos += 1; }
+        // Bordas ligadas ao outro lado
+        let vizinhos = self.grade.neighbors8((pos.0 as i32, pos.1 as i32), EdgePolicy::Wrap)
+            .filter(|(_, viva)| **viva)
+            .count();
 
         if vizinhos == 3 { 
             return true; 
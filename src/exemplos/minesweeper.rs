@@ -4,7 +4,7 @@ use rand::Rng;
 
 use crossterm::{cursor::*, event::*, queue, style::*, terminal::{self, Clear, ClearType}, ExecutableCommand, QueueableCommand};
 
-use crate::{estruturas::{Dir, GraphIter, GraphIterState, Iterator2D, Vec2D}, utils::{Terminal, TerminalHandler}};
+use crate::{estruturas::{Dir, EdgePolicy, GraphIter, GraphIterState, Iterator2D, Vec2D}, utils::{Terminal, TerminalHandler}};
 
 #[derive(Clone)]
 enum GradeCell {
@@ -56,17 +56,6 @@ impl MineSweeperGame {
     // =================================================================
     // Métodos da lógica do jogo
     // =================================================================
-    fn is_mine(grade: &Vec2D<GradeCell>, (x,y): (i32,i32)) -> bool {
-        if let Some(cell) = grade.get((x,y)) {
-            match cell {
-                GradeCell::Empty { minas, explorado } => false,
-                GradeCell::Mine => true,
-            }
-        } else {
-            false
-        }
-    }
-
     fn is_mine_or_unexplored(&self, (x,y): (usize,usize)) -> bool {
         if let Some(cell) = self.grade.get((x as i32,y as i32)) {
             match cell {
@@ -105,16 +94,10 @@ impl MineSweeperGame {
         for pos in grade.positions() {
             let grade_value = &grade[pos];
             if let GradeCell::Empty { minas, explorado } = grade_value {
-                let mut minas = 0;
                 let (x,y) = (pos.0 as i32, pos.1 as i32);
-                if Self::is_mine(grade,(x-1, y-1)) { minas += 1; }
-                if Self::is_mine(grade,(x  , y-1)) { minas += 1; }
-                if Self::is_mine(grade,(x+1, y-1)) { minas += 1; }
-                if Self::is_mine(grade,(x-1, y  )) { minas += 1; }
-                if Self::is_mine(grade,(x+1, y  )) { minas += 1; }
-                if Self::is_mine(grade,(x-1, y+1)) { minas += 1; }
-                if Self::is_mine(grade,(x  , y+1)) { minas += 1; }
-                if Self::is_mine(grade,(x+1, y+1)) { minas += 1; }
+                let minas = grade.neighbors8((x,y), EdgePolicy::Clip)
+                    .filter(|(_, cell)| matches!(cell, GradeCell::Mine))
+                    .count() as i32;
 
                 grade[pos] = GradeCell::Empty { minas: minas, explorado: false };
             }
@@ -124,7 +107,6 @@ impl MineSweeperGame {
     }
 
     fn visit<S: GraphIterState<(usize,usize)>>(&mut self, (gx,gy): (usize,usize), iter: &mut S) {
-        let (w,h) = self.grade.size();
         if let GradeCell::Empty { minas, explorado } = &mut self.grade[(gx,gy)] {
             // explorar ele próprio
             if !*explorado {
@@ -137,17 +119,9 @@ impl MineSweeperGame {
                 self.marcacoes.remove(&(gx,gy));
 
                 if *minas == 0 { // Só possui vizinhos se for 0
-                    let (w,h) = (w as i32, h as i32);
-                    let (gx, gy) = (gx as i32, gy as i32);
-                    [
-                        (gx-1, gy-1), (gx  ,gy-1), (gx+1, gy-1),
-                        (gx-1, gy  ),              (gx+1, gy  ),
-                        (gx-1, gy+1), (gx  ,gy+1), (gx+1, gy+1)
-                    ].into_iter().for_each(|(x,y)| {
-                        if x >= 0 && y >= 0 && x < w && y < h {
-                            iter.push_neighbor((x as usize, y as usize));
-                        }
-                    });
+                    for ((x,y), _) in self.grade.neighbors8((gx as i32, gy as i32), EdgePolicy::Clip) {
+                        iter.push_neighbor((x as usize, y as usize));
+                    }
                 }
             }
         }
@@ -156,7 +130,8 @@ impl MineSweeperGame {
     fn explorar(&mut self, (gx,gy): (usize,usize)) {
         match &mut self.grade[(gx,gy)] {
             GradeCell::Empty { minas, explorado } => {
-                let mut iter = GraphIter::breadth_first((gx, gy));
+                let (w,h) = self.grade.size();
+                let mut iter = GraphIter::breadth_first((gx, gy)).with_visited(Vec2D::new(w, h, false));
                 while let Some(pos) = iter.next() {
                     self.visit(pos, &mut iter);
                 }
@@ -3,36 +3,38 @@ use std::ops::{Index, IndexMut};
 use crate::estruturas::Vec2D;
 
 pub struct QuadroSudoku {
-    table: [usize; 81]
+    table: Vec2D<usize>
 }
 
 impl QuadroSudoku {
     pub fn new() -> Self {
         Self {
-            table: [0; 81]
+            table: Vec2D::new(9, 9, 0)
         }
     }
 
     pub fn from_slice(table: &[usize]) -> Self {
         let mut quadro = Self::new();
-        quadro.table.copy_from_slice(table);
+        for y in 0..9 {
+            quadro.table.row_mut(y).copy_from_slice(&table[y*9..(y+1)*9]);
+        }
         quadro
     }
 
     pub fn print(&self) {
-        for (i, &v) in self.table.iter().enumerate() {
-            if v == 0 {
-                print!(". ");
-            } else {
-                print!("{} ", v);
-            }
-            if (i+1) % 3 == 0 {
-                print!("\t");
-            }
-            if (i+1) % 9 == 0 {
-                println!();
+        for y in 0..9 {
+            for (x, &v) in self.table.row(y).iter().enumerate() {
+                if v == 0 {
+                    print!(". ");
+                } else {
+                    print!("{} ", v);
+                }
+                if (x+1) % 3 == 0 {
+                    print!("\t");
+                }
             }
-            if (i+1) % 27 == 0 {
+            println!();
+            if (y+1) % 3 == 0 {
                 println!();
             }
         }
@@ -40,14 +42,14 @@ impl QuadroSudoku {
 }
 
 pub struct Possib<const N: usize> {
-    index: usize, // índice da possibilidade no quadro
+    pos: (usize,usize), // posição da possibilidade no quadro
     p: [bool; N] // possibilidades, true se for possível
 }
 
 impl<const N: usize> Possib<N> {
-    pub fn new(index: usize) -> Self {
+    pub fn new(pos: (usize,usize)) -> Self {
         Self {
-            index: index,
+            pos: pos,
             p: [true; N]
         }
     }
@@ -68,7 +70,7 @@ impl<const N: usize> Possib<N> {
 }
 
 trait PencilMark<const N: usize> {
-    fn solucionar(depth: usize, quadro: &mut [usize]) -> bool {
+    fn solucionar(depth: usize, quadro: &mut Vec2D<usize>) -> bool {
         let possib = Self::obter_melhor(quadro);
         if let None = possib {
             // Se não tem mais possibilidades, não tem como continuar
@@ -81,7 +83,7 @@ trait PencilMark<const N: usize> {
             if !possib.p[k] { continue; } // Se não é possível, ignora
 
             // Marca a possibilidade
-            quadro[possib.index] = k + 1;
+            quadro[possib.pos] = k + 1;
 
             if Self::checar_solucionado(quadro) {
                 return true;
@@ -92,7 +94,7 @@ trait PencilMark<const N: usize> {
             }
 
             // Desmarca a possibilidade, para tentar a próxima
-            quadro[possib.index] = 0;
+            quadro[possib.pos] = 0;
         }
 
 	    // Nenhuma escolha foi válida para este quadrado, ou seja, é ímpossível solucionar nesta configuração
@@ -100,22 +102,19 @@ trait PencilMark<const N: usize> {
     }
 
     // Se não tem nenhum quadrado sem escolher, está solucionado
-    fn checar_solucionado(quadro: &[usize]) -> bool {
-        for i in 0..quadro.len() {
-            if quadro[i] == 0 { return false; }
-        }
-        true
+    fn checar_solucionado(quadro: &Vec2D<usize>) -> bool {
+        quadro.values().all(|&v| v != 0)
     }
 
     // Ao mesmo tempo que analisa as possibilidades, encontra a com menor entropia
-    fn obter_melhor(quadro: &[usize]) -> Option<Possib<N>>
+    fn obter_melhor(quadro: &Vec2D<usize>) -> Option<Possib<N>>
     {
         let mut min_p: Option<Possib<N>> = None;
         let mut min_cont: i32 = -1;
-        for index in 0..quadro.len() {
-            if quadro[index] != 0 { continue; } // Se já foi escolhido ignora
+        for pos in quadro.positions() {
+            if quadro[pos] != 0 { continue; } // Se já foi escolhido ignora
 
-            let mut p = Possib::new(index);
+            let mut p = Possib::new(pos);
             Self::regras_quadro(quadro, &mut p);
 
             let cont = p.contar();
@@ -128,52 +127,28 @@ trait PencilMark<const N: usize> {
         min_p
     }
 
-    fn regras_quadro(quadro: &[usize], p: &mut Possib<N>);
+    fn regras_quadro(quadro: &Vec2D<usize>, p: &mut Possib<N>);
 }
 
 impl PencilMark<9> for QuadroSudoku {
-    fn regras_quadro(quadro: &[usize], possibs: &mut Possib<9>) {
-        let index = possibs.index;
+    fn regras_quadro(quadro: &Vec2D<usize>, possibs: &mut Possib<9>) {
+        let (px, py) = possibs.pos;
         // Se já foi escolhido no quadro, só tem aquela opção disponível
-        if quadro[index] != 0 {
+        if quadro[(px, py)] != 0 {
             possibs.resetar(false);
-            possibs.p[quadro[index]-1] = true;
+            possibs.p[quadro[(px, py)]-1] = true;
             return
         }
 
-        let py = index / 9;
-        let px = index % 9;
-
-        // Verifica colunas
-        for y in 0..9 {
-            if y == py { continue; }
-            let quadro_v = quadro[y * 9 + px];
+        // Verifica coluna, linha e quadrado (o próprio é 0, então não atrapalha)
+        let coluna = quadro.column(px);
+        let linha = quadro.row(py).iter();
+        let quadrado = quadro.view((((px / 3) * 3, (py / 3) * 3), (3, 3)));
+        for &quadro_v in coluna.chain(linha).chain(quadrado.values()) {
             if quadro_v == 0 { continue; }
-            
-            possibs.p[quadro_v - 1] = false;
-        }
 
-        // Verifica linhas
-        for x in 0..9 {
-            if x == px { continue; }
-            let quadro_v = quadro[py * 9 + x];
-            if quadro_v == 0 { continue; }
-            
             possibs.p[quadro_v - 1] = false;
         }
-
-        // verificar quadrado
-        let quadx = (px / 3) * 3;
-        let quady = (py / 3) * 3;
-        for x in quadx..(quadx+3) {
-            for y in quady..(quady+3) {
-                if x == px || y == py { continue; }
-                let quadro_v = quadro[y*9 + x];
-                if quadro_v == 0 { continue; }
-
-                possibs.p[quadro_v-1] = false;
-            }
-        }
     }
 }
 